@@ -1,7 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Token, TokenAccount, Mint};
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{
+    get_stack_height, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT,
+};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_spl::token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{
+    self, Burn, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
 
-declare_id!("BurnGigaBrain11111111111111111111111111111111");
+declare_id!("BurnGigaBrain1111111111111111111111111111111");
 
 /// GigaBrain AI Trading Bot - Autonomous Token Burn Program
 /// 
@@ -15,16 +30,26 @@ pub mod gigabrain_burn {
     use super::*;
 
     /// Initialize a new burn configuration for an AI trading bot
+    ///
+    /// Configs live at `["burn_config", authority, token_mint, config_id]`, so
+    /// any number of authorities and strategies can target the same mint.
     /// 
     /// # Arguments
-    /// * `profit_threshold` - Minimum profit (in basis points) to trigger burn
+    /// * `config_id` - Caller-chosen id distinguishing this authority's configs
+    /// * `profit_threshold` - Minimum profit to trigger a burn: an absolute amount,
+    ///   or ROI in basis points once the config uses `ProfitMode::RoiBps`
     /// * `burn_percentage` - Percentage of profits to burn (0-10000 = 0-100%)
     /// * `min_burn_amount` - Minimum token amount for a burn transaction
+    /// * `payment_amount` - Required x402 payment per burn (in payment mint base units)
+    /// * `profit_oracle` - Key whose Ed25519 signature attests profit reports
     pub fn initialize_burn_config(
         ctx: Context<InitializeBurnConfig>,
+        config_id: u64,
         profit_threshold: u64,
         burn_percentage: u16,
         min_burn_amount: u64,
+        payment_amount: u64,
+        profit_oracle: Pubkey,
     ) -> Result<()> {
         require!(burn_percentage <= 10000, ErrorCode::InvalidBurnPercentage);
         
@@ -37,37 +62,222 @@ pub mod gigabrain_burn {
         config.total_burned = 0;
         config.burn_count = 0;
         config.bump = ctx.bumps.burn_config;
+        config.payment_mint = ctx.accounts.payment_mint.key();
+        config.payment_treasury = ctx.accounts.payment_treasury.key();
+        config.payment_amount = payment_amount;
+        config.profit_oracle = profit_oracle;
+        config.last_profit_period = 0;
+        config.pending_authority = None;
+        config.vault = None;
+        config.creator = ctx.accounts.authority.key();
+        config.config_id = config_id;
+        config.payment_mode = PaymentMode::VerifiedTransfer;
+        config.credit_vault = None;
+        config.price_feed = None;
+        config.profit_mode = ProfitMode::Absolute;
+        config.profit_source = ProfitSource::Attested;
+        config.ai_signer = None;
+        config.guardians = Vec::new();
+        config.swap_programs = Vec::new();
 
-        msg!("✅ Burn config initialized for mint: {}", config.token_mint);
-        msg!("   Profit threshold: {} basis points", profit_threshold);
+        msg!("✅ Burn config #{} initialized for mint: {}", config_id, config.token_mint);
+        msg!("   Profit threshold: {}", profit_threshold);
         msg!("   Burn percentage: {}%", burn_percentage as f64 / 100.0);
         msg!("   Min burn amount: {}", min_burn_amount);
+        msg!("   x402 payment: {} to {}", payment_amount, config.payment_treasury);
+        msg!("   Profit oracle: {}", profit_oracle);
+
+        Ok(())
+    }
+
+    /// Move a legacy mint-only burn config to the namespaced PDA
+    ///
+    /// Configs created by the original program live at `["burn_config",
+    /// token_mint]` and only hold thresholds and totals. This copies them into
+    /// a new config at `["burn_config", authority, token_mint, config_id]`,
+    /// takes the x402 payment settings and profit oracle the legacy layout
+    /// lacks (as `initialize_burn_config` does), leaves every later setting at
+    /// its default, and closes the legacy account.
+    ///
+    /// # Arguments
+    /// * `config_id` - Id of the new namespaced config
+    /// * `payment_amount` - Required x402 payment per burn (in payment mint base units)
+    /// * `profit_oracle` - Key whose Ed25519 signature attests profit reports
+    pub fn migrate_burn_config(
+        ctx: Context<MigrateBurnConfig>,
+        config_id: u64,
+        payment_amount: u64,
+        profit_oracle: Pubkey,
+    ) -> Result<()> {
+        let legacy_info = ctx.accounts.legacy_config.to_account_info();
+        let legacy = LegacyBurnConfig::load(&legacy_info)?;
+        require_keys_eq!(legacy.authority, ctx.accounts.authority.key(), ErrorCode::NotConfigAuthority);
+        require_keys_eq!(legacy.token_mint, ctx.accounts.token_mint.key(), ErrorCode::InvalidLegacyConfig);
+
+        let burn_config_key = ctx.accounts.burn_config.key();
+
+        ctx.accounts.burn_config.set_inner(BurnConfig {
+            authority: legacy.authority,
+            token_mint: legacy.token_mint,
+            profit_threshold: legacy.profit_threshold,
+            burn_percentage: legacy.burn_percentage,
+            min_burn_amount: legacy.min_burn_amount,
+            total_burned: legacy.total_burned,
+            burn_count: legacy.burn_count,
+            bump: ctx.bumps.burn_config,
+            payment_mint: ctx.accounts.payment_mint.key(),
+            payment_treasury: ctx.accounts.payment_treasury.key(),
+            payment_amount,
+            profit_oracle,
+            last_profit_period: 0,
+            pending_authority: None,
+            vault: None,
+            max_burn_amount: 0,
+            window_seconds: 0,
+            window_max_burned: 0,
+            min_burn_interval: 0,
+            window_start: 0,
+            window_burned: 0,
+            last_burn_at: 0,
+            max_supply_bps: 0,
+            paused: false,
+            auto_pause_bps: 0,
+            creator: legacy.authority,
+            config_id,
+            payment_mode: PaymentMode::VerifiedTransfer,
+            credit_vault: None,
+            credit_fees_accrued: 0,
+            price_feed: None,
+            quote_decimals: 0,
+            max_price_age: 0,
+            max_price_confidence_bps: 0,
+            profit_mode: ProfitMode::Absolute,
+            cumulative_profit: 0,
+            high_water_mark: 0,
+            drawdown_reset_bps: 0,
+            profit_source: ProfitSource::Attested,
+            ai_signer: None,
+            min_ai_confidence: 0,
+            require_positive_sentiment: false,
+            guardians: Vec::new(),
+            guardian_quorum: 0,
+            quorum_burn_threshold: 0,
+            swap_programs: Vec::new(),
+            ai_decision_nonce: 0,
+        });
+
+        close_program_account(&legacy_info, &ctx.accounts.authority.to_account_info())?;
+
+        msg!("✅ Migrated burn config {} -> {}", legacy_info.key(), burn_config_key);
+
+        emit!(BurnConfigMigrated {
+            legacy_config: legacy_info.key(),
+            burn_config: burn_config_key,
+            authority: legacy.authority,
+            config_id,
+        });
 
         Ok(())
     }
 
     /// Execute autonomous burn with x402 payment verification
+    ///
+    /// How the x402 fee is paid depends on the config's `payment_mode`:
+    /// - `VerifiedTransfer`: the instruction immediately preceding this one
+    ///   must be an SPL Token `transfer_checked` of at least `payment_amount`
+    ///   of the payment mint from `payer` into the configured payment treasury.
+    /// - `ProgramCollected`: the program itself transfers `payment_amount` from
+    ///   `payment_source` to the payment treasury, so payment and burn succeed
+    ///   or fail together.
+    /// - `PrepaidCredits`: `payment_amount` is debited from the payer's
+    ///   `CreditAccount`; no token transfer happens in the burn transaction.
+    ///
+    /// In `VerifiedTransfer` mode a permanent `PaymentReceipt` PDA seeded by
+    /// the hash of `x402_signature` is created here, so the same payment proof
+    /// can never fund a second burn. The other modes take the fee on-chain and only
+    /// create a receipt when one is passed.
+    ///
+    /// The profit that triggered the burn is not taken from the caller: the
+    /// transaction must also carry an Ed25519 program instruction in which the
+    /// config's `profit_oracle` signs a `ProfitReport` for this config (a
+    /// `RoiReport` in `ProfitMode::RoiBps`). Each report period can only be
+    /// used once.
+    ///
+    /// The executor is either the config authority or a registered
+    /// `BurnOperator` holding the burn permission. Burns of at least
+    /// `quorum_burn_threshold` also need `guardian_quorum` of the config's
+    /// guardians to sign, passed as signer `remaining_accounts`. Tokens are
+    /// burned from an account the executor owns or has been approved to spend
+    /// as SPL delegate, or from the config's program-owned burn vault.
+    ///
+    /// The amount is resolved from `mode` against the source balance at
+    /// execution time. `BurnMode::All` also closes an executor-owned source,
+    /// returning its rent to the executor; the burn vault is emptied but kept.
     /// 
     /// # Arguments
-    /// * `amount` - Amount of tokens to burn
+    /// * `mode` - Absolute amount, share of the source balance, or everything
     /// * `x402_signature` - Payment verification signature from x402 service
-    /// * `profit_amount` - Current trading profit that triggered the burn
     pub fn execute_autonomous_burn(
         ctx: Context<ExecuteAutonomousBurn>,
-        amount: u64,
+        mode: BurnMode,
         x402_signature: String,
-        profit_amount: u64,
     ) -> Result<()> {
         let config = &ctx.accounts.burn_config;
+        let amount = mode.resolve(ctx.accounts.token_account.amount)?;
+
+        // Circuit breakers: program-wide and per-config pause
+        config.check_active(&ctx.accounts.global_config)?;
 
         // Verify burn meets minimum threshold
         require!(amount >= config.min_burn_amount, ErrorCode::BelowMinBurnAmount);
 
-        // Verify profit threshold met
-        require!(profit_amount >= config.profit_threshold, ErrorCode::ProfitThresholdNotMet);
+        // Establish the profit behind this burn: oracle report or PnL ledger
+        let trigger = ctx.accounts.profit_trigger()?;
+
+        // The registered AI signer must approve this specific burn
+        let ai_decision =
+            load_ai_decision(config, &ctx.accounts.instructions.to_account_info(), amount)?;
+        let profit_amount = trigger.profit_amount;
+        let roi_bps = trigger.roi_bps;
+
+        // Verify profit threshold met: absolute profit, or ROI in basis points
+        let threshold_metric = roi_bps.unwrap_or(profit_amount);
+        require!(
+            threshold_metric >= config.profit_threshold,
+            ErrorCode::ProfitThresholdNotMet
+        );
+
+        // Only profit above the high-water mark is owed a burn
+        let (cumulative_profit, eligible_profit) =
+            config.profit_above_high_water_mark(trigger.pnl)?;
+        require!(eligible_profit > 0, ErrorCode::NoProfitAboveHighWaterMark);
+
+        // Convert the quote-denominated profit into token base units
+        let profit_tokens = match config.price_feed {
+            Some(feed) => {
+                let feed_account = ctx
+                    .accounts
+                    .price_feed
+                    .as_ref()
+                    .ok_or(ErrorCode::PriceFeedMissing)?;
+                require_keys_eq!(feed_account.key(), feed, ErrorCode::InvalidPriceFeed);
+                let price = PythPrice::load(feed_account)?;
+                price.check(
+                    Clock::get()?.unix_timestamp,
+                    config.max_price_age,
+                    config.max_price_confidence_bps,
+                )?;
+                price.quote_to_tokens(
+                    eligible_profit,
+                    config.quote_decimals,
+                    ctx.accounts.token_mint.decimals,
+                )?
+            }
+            None => eligible_profit,
+        };
 
         // Calculate expected burn amount from profit
-        let expected_burn = (profit_amount as u128)
+        let expected_burn = (profit_tokens as u128)
             .checked_mul(config.burn_percentage as u128)
             .unwrap()
             .checked_div(10000)
@@ -75,51 +285,714 @@ pub mod gigabrain_burn {
 
         require!(amount >= expected_burn, ErrorCode::InsufficientBurnAmount);
 
-        // Verify x402 micropayment (in production, this would verify signature)
-        msg!("🔒 x402 Payment Verified: {}", x402_signature);
+        // Authorize the executor: the config authority or an active operator
+        let executor = ctx.accounts.executor.key();
+        if executor != config.authority {
+            let operator = ctx
+                .accounts
+                .burn_operator
+                .as_mut()
+                .ok_or(ErrorCode::UnauthorizedExecutor)?;
+            operator.authorize(BurnOperator::PERMISSION_BURN, Clock::get()?.unix_timestamp)?;
+            operator.allowance = operator
+                .allowance
+                .checked_sub(amount)
+                .ok_or(ErrorCode::OperatorAllowanceExceeded)?;
+        }
+
+        // Large burns also need M-of-N guardian signatures
+        if config.requires_quorum(amount) {
+            config.check_quorum(ctx.remaining_accounts)?;
+        }
+
+        let from_vault = config.vault == Some(ctx.accounts.token_account.key());
+        if !from_vault {
+            check_burn_source(&ctx.accounts.token_account, &executor, amount)?;
+        }
+        let close_source = mode == BurnMode::All && !from_vault;
+        if close_source {
+            require_keys_eq!(
+                ctx.accounts.token_account.owner,
+                executor,
+                ErrorCode::CloseRequiresOwner
+            );
+        }
+
+        // Protocol fee: a share of the burn goes to the fee destination
+        // Vault tokens only ever leave by being burned, so vault burns are fee-free
+        let exempt = from_vault || ctx.accounts.fee_exemption.is_some();
+        let fee_schedule = load_if_initialized::<FeeSchedule>(&ctx.accounts.fee_schedule)?;
+        let protocol_fee = fee_schedule
+            .as_ref()
+            .map_or(0, |schedule| schedule.fee_for(amount, config.burn_count, exempt));
+        let burn_amount = amount - protocol_fee;
+
+        // Verify or collect the x402 micropayment within this transaction
+        let paid = ctx.accounts.take_x402_payment()?;
+        msg!("🔒 x402 Payment Verified: {} ({} paid)", x402_signature, paid);
+
+        let burn_config_key = ctx.accounts.burn_config.key();
+        if let Some(receipt) = ctx.accounts.payment_receipt.as_mut() {
+            receipt.payer = ctx.accounts.payer.key();
+            receipt.amount = paid;
+            receipt.burn_config = burn_config_key;
+            receipt.slot = Clock::get()?.slot;
+            receipt.bump = ctx.bumps.payment_receipt;
+        }
+
+        // Enforce on-chain burn caps and record the burn
+        let supply_before = ctx.accounts.token_mint.supply;
+        let source_balance = ctx.accounts.token_account.amount;
+        ctx.accounts
+            .burn_config
+            .record_burn(burn_amount, supply_before, Clock::get()?.unix_timestamp)?;
+
+        if let Some(schedule) = fee_schedule.as_ref().filter(|_| protocol_fee > 0) {
+            ctx.accounts.collect_protocol_fee(schedule, protocol_fee)?;
+        }
 
         // Execute SPL token burn
-        let cpi_accounts = Burn {
-            mint: ctx.accounts.token_mint.to_account_info(),
-            from: ctx.accounts.token_account.to_account_info(),
-            authority: ctx.accounts.authority.to_account_info(),
-        };
+        if from_vault {
+            burn_from_vault(
+                &ctx.accounts.burn_config,
+                ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.token_mint,
+                ctx.accounts.token_account.to_account_info(),
+                burn_amount,
+            )?;
+        } else {
+            let cpi_accounts = Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.executor.to_account_info(),
+            };
+
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+            burn_checked(cpi_ctx, burn_amount, ctx.accounts.token_mint.decimals)?;
+        }
+
+        if close_source {
+            let cpi_accounts = CloseAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                destination: ctx.accounts.executor.to_account_info(),
+                authority: ctx.accounts.executor.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token_interface::close_account(cpi_ctx)?;
+            msg!("   Source account closed");
+        }
 
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        ctx.accounts.token_mint.reload()?;
+        let supply_after = ctx.accounts.token_mint.supply;
 
-        token::burn(cpi_ctx, amount)?;
+        if let (Some(ledger), Some((from_sequence, to_sequence))) =
+            (&ctx.accounts.pnl_ledger, trigger.ledger_range)
+        {
+            ledger.load_mut()?.consumed_sequence = to_sequence;
+            emit!(LedgerProfitConsumed {
+                burn_config: ctx.accounts.burn_config.key(),
+                from_sequence,
+                to_sequence,
+                profit_amount,
+            });
+        }
 
-        // Update burn statistics
         let config = &mut ctx.accounts.burn_config;
-        config.total_burned = config.total_burned.checked_add(amount).unwrap();
-        config.burn_count = config.burn_count.checked_add(1).unwrap();
+        config.last_profit_period = trigger.period_id;
+        config.cumulative_profit = cumulative_profit;
+        config.high_water_mark = cumulative_profit;
+        if ai_decision.is_some() {
+            config.ai_decision_nonce += 1;
+        }
+        if config.payment_mode == PaymentMode::PrepaidCredits {
+            config.credit_fees_accrued = config.credit_fees_accrued.checked_add(paid).unwrap();
+        }
+
+        if config.should_auto_pause(amount, source_balance) {
+            config.paused = true;
+            msg!("⚠️ Auto-pause tripped: burn of {} out of {} balance", amount, source_balance);
+            emit!(BurnsPaused {
+                burn_config: Some(config.key()),
+                by: executor,
+                auto_tripped: true,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
 
         msg!("🔥 Autonomous Burn Executed!");
-        msg!("   Amount burned: {}", amount);
-        msg!("   Profit trigger: {}", profit_amount);
+        msg!("   Amount burned: {}", burn_amount);
+        if protocol_fee > 0 {
+            msg!("   Protocol fee: {}", protocol_fee);
+        }
+        msg!("   Profit trigger: {} (period {})", profit_amount, trigger.period_id);
+        msg!("   Profit above high-water mark: {}", eligible_profit);
+        if let Some(decision) = &ai_decision {
+            msg!("   AI confidence: {}% ({:?})", decision.confidence, decision.sentiment);
+        }
+        if let Some(roi_bps) = roi_bps {
+            msg!("   ROI: {}%", roi_bps as f64 / 100.0);
+        }
         msg!("   Total burned: {}", config.total_burned);
         msg!("   Burn count: {}", config.burn_count);
 
         emit!(BurnEvent {
-            authority: ctx.accounts.authority.key(),
+            authority: config.authority,
+            executor,
             token_mint: ctx.accounts.token_mint.key(),
-            amount,
+            amount: burn_amount,
+            protocol_fee,
             profit_amount,
+            profit_period: trigger.period_id,
             total_burned: config.total_burned,
             burn_count: config.burn_count,
+            supply_before,
+            supply_after,
+            cumulative_profit: config.cumulative_profit,
+            high_water_mark: config.high_water_mark,
+            ai_reasoning_hash: ai_decision.map(|decision| decision.reasoning_hash),
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
+    /// Create the config's realized PnL ledger
+    ///
+    /// The ledger is a zero-copy ring buffer at `["pnl_ledger", burn_config]`
+    /// holding up to `PnlLedger::CAPACITY` unconsumed trades, all quoted in
+    /// `quote_mint`.
+    pub fn initialize_pnl_ledger(ctx: Context<InitializePnlLedger>) -> Result<()> {
+        let mut ledger = ctx.accounts.pnl_ledger.load_init()?;
+        ledger.burn_config = ctx.accounts.burn_config.key();
+        ledger.quote_mint = ctx.accounts.quote_mint.key();
+        ledger.bump = ctx.bumps.pnl_ledger;
+
+        msg!("✅ PnL ledger initialized, quoted in {}", ledger.quote_mint);
+
+        Ok(())
+    }
+
+    /// Append a realized trade to the config's PnL ledger
+    ///
+    /// Called by the config authority or an operator holding the
+    /// record-trades permission. Fails once `PnlLedger::CAPACITY` trades are
+    /// unconsumed, until a burn or `settle_pnl_ledger` consumes them; every
+    /// trade is also emitted as `TradeRecorded`.
+    ///
+    /// # Arguments
+    /// * `entry_amount` - Quote spent opening the position (cost basis)
+    /// * `exit_amount` - Quote received closing it
+    pub fn record_trade(
+        ctx: Context<RecordTrade>,
+        entry_amount: u64,
+        exit_amount: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        if ctx.accounts.recorder.key() != ctx.accounts.burn_config.authority {
+            ctx.accounts
+                .burn_operator
+                .as_ref()
+                .ok_or(ErrorCode::UnauthorizedExecutor)?
+                .authorize(BurnOperator::PERMISSION_RECORD_TRADES, now)?;
+        }
+
+        let mut ledger = ctx.accounts.pnl_ledger.load_mut()?;
+        require_keys_eq!(
+            ctx.accounts.quote_mint.key(),
+            ledger.quote_mint,
+            ErrorCode::InvalidQuoteMint
+        );
+        let trade = TradeRecord {
+            sequence: ledger.next_sequence,
+            entry_amount,
+            exit_amount,
+            timestamp: now,
+            quote_mint: ledger.quote_mint,
+        };
+        ledger.push(trade)?;
+
+        msg!("📒 Trade #{} recorded: {} -> {}", trade.sequence, entry_amount, exit_amount);
+
+        emit!(TradeRecorded {
+            burn_config: ctx.accounts.burn_config.key(),
+            sequence: trade.sequence,
+            entry_amount,
+            exit_amount,
+            quote_mint: trade.quote_mint,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Settle the PnL ledger's unconsumed trades without burning
+    ///
+    /// Adds their net `proceeds - cost_basis` to the config's cumulative
+    /// profit under the same drawdown policy as `record_profit_period`, and
+    /// marks them consumed. This is how a losing range, which can never
+    /// trigger a burn, leaves the ledger.
+    pub fn settle_pnl_ledger(ctx: Context<SettlePnlLedger>) -> Result<()> {
+        let mut ledger = ctx.accounts.pnl_ledger.load_mut()?;
+        let (from_sequence, to_sequence) = ledger.unconsumed_range();
+        require!(from_sequence < to_sequence, ErrorCode::NoLedgerTrades);
+        let (cost_basis, proceeds) = ledger.totals(from_sequence, to_sequence);
+        let pnl = i64::try_from(proceeds as i128 - cost_basis as i128)
+            .map_err(|_| error!(ErrorCode::ProfitLedgerOverflow))?;
+        ledger.consumed_sequence = to_sequence;
+
+        let config = &mut ctx.accounts.burn_config;
+        config.record_pnl(pnl)?;
+
+        msg!("📒 Ledger trades {}..{} settled: {}", from_sequence, to_sequence, pnl);
+        msg!("   Cumulative profit: {}", config.cumulative_profit);
+        msg!("   High-water mark: {}", config.high_water_mark);
+
+        emit!(LedgerSettled {
+            burn_config: config.key(),
+            from_sequence,
+            to_sequence,
+            pnl,
+            cumulative_profit: config.cumulative_profit,
+            high_water_mark: config.high_water_mark,
+        });
+
+        Ok(())
+    }
+
+    /// Record an attested profit or loss period without burning
+    ///
+    /// Consumes a `RoiReport` signed by the profit oracle, in either profit
+    /// mode, and adds `proceeds - cost_basis` to the config's cumulative
+    /// profit. A loss that takes cumulative profit below the high-water mark
+    /// lowers the mark by `drawdown_reset_bps` of the drawdown.
+    pub fn record_profit_period(ctx: Context<RecordProfitPeriod>) -> Result<()> {
+        let config = &ctx.accounts.burn_config;
+        let report: RoiReport = load_ed25519_attestation(
+            &ctx.accounts.instructions.to_account_info(),
+            &config.profit_oracle,
+            |report: &RoiReport| report.burn_config == config.key(),
+        )?
+        .ok_or(ErrorCode::ProfitAttestationMissing)?;
+        require!(
+            report.expiry_slot >= Clock::get()?.slot,
+            ErrorCode::ProfitAttestationExpired
+        );
+        require!(
+            report.period_id > config.last_profit_period,
+            ErrorCode::ProfitPeriodAlreadyUsed
+        );
+        let pnl = report.pnl()?;
+
+        let config = &mut ctx.accounts.burn_config;
+        config.last_profit_period = report.period_id;
+        config.record_pnl(pnl)?;
+
+        msg!("📒 Profit period {} recorded: {}", report.period_id, pnl);
+        msg!("   Cumulative profit: {}", config.cumulative_profit);
+        msg!("   High-water mark: {}", config.high_water_mark);
+
+        emit!(ProfitPeriodRecorded {
+            burn_config: config.key(),
+            period_id: report.period_id,
+            pnl,
+            cumulative_profit: config.cumulative_profit,
+            high_water_mark: config.high_water_mark,
+        });
+
+        Ok(())
+    }
+
+    /// Burn everything left in a token account and close it
+    ///
+    /// For retiring an agent's token account or the config's burn vault: the
+    /// remaining balance is burned under the config's pause switches and caps,
+    /// then the account is closed and its rent sent to `destination`. The
+    /// executor must own the account (and be the authority or a burn
+    /// operator); only the authority may close the burn vault.
+    ///
+    /// A retirement burn needs no profit trigger, but a non-empty balance is
+    /// otherwise charged like `execute_autonomous_burn`: the x402 fee per the
+    /// config's `payment_mode` (with a receipt for `x402_signature` in
+    /// `VerifiedTransfer` mode) and, outside the burn vault, the protocol fee.
+    /// It also needs the guardian quorum (as signer remaining accounts) at
+    /// `quorum_burn_threshold` or more, and an `AiDecision` when the config
+    /// has an `ai_signer`. Closing an empty account is free.
+    ///
+    /// # Arguments
+    /// * `x402_signature` - Payment verification signature from x402 service
+    pub fn burn_all_and_close(ctx: Context<BurnAllAndClose>, x402_signature: String) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.burn_config;
+        config.check_active(&ctx.accounts.global_config)?;
+
+        let executor = ctx.accounts.executor.key();
+        let amount = ctx.accounts.token_account.amount;
+        let from_vault = config.vault == Some(ctx.accounts.token_account.key());
+        if from_vault {
+            require_keys_eq!(executor, config.authority, ErrorCode::NotConfigAuthority);
+        } else {
+            require_keys_eq!(
+                ctx.accounts.token_account.owner,
+                executor,
+                ErrorCode::CloseRequiresOwner
+            );
+            if executor != config.authority {
+                let operator = ctx
+                    .accounts
+                    .burn_operator
+                    .as_mut()
+                    .ok_or(ErrorCode::UnauthorizedExecutor)?;
+                operator.authorize(BurnOperator::PERMISSION_BURN, now)?;
+                operator.allowance = operator
+                    .allowance
+                    .checked_sub(amount)
+                    .ok_or(ErrorCode::OperatorAllowanceExceeded)?;
+            }
+        }
+
+        let mut ai_decision = None;
+        let mut protocol_fee = 0;
+        if amount > 0 {
+            let config = &ctx.accounts.burn_config;
+            if config.requires_quorum(amount) {
+                config.check_quorum(ctx.remaining_accounts)?;
+            }
+            ai_decision =
+                load_ai_decision(config, &ctx.accounts.instructions.to_account_info(), amount)?;
+            if ai_decision.is_some() {
+                ctx.accounts.burn_config.ai_decision_nonce += 1;
+            }
+
+            // Same charges as any other burn, so retiring is no way around them
+            let exempt = from_vault || ctx.accounts.fee_exemption.is_some();
+            let fee_schedule = load_if_initialized::<FeeSchedule>(&ctx.accounts.fee_schedule)?;
+            protocol_fee = fee_schedule.as_ref().map_or(0, |schedule| {
+                schedule.fee_for(amount, ctx.accounts.burn_config.burn_count, exempt)
+            });
+            let paid = ctx.accounts.take_x402_payment()?;
+            msg!("🔒 x402 Payment Verified: {} ({} paid)", x402_signature, paid);
+
+            let burn_config_key = ctx.accounts.burn_config.key();
+            if let Some(receipt) = ctx.accounts.payment_receipt.as_mut() {
+                receipt.payer = ctx.accounts.payer.key();
+                receipt.amount = paid;
+                receipt.burn_config = burn_config_key;
+                receipt.slot = Clock::get()?.slot;
+                receipt.bump = ctx.bumps.payment_receipt;
+            }
+            let config = &mut ctx.accounts.burn_config;
+            if config.payment_mode == PaymentMode::PrepaidCredits {
+                config.credit_fees_accrued = config.credit_fees_accrued.checked_add(paid).unwrap();
+            }
+
+            let burn_amount = amount - protocol_fee;
+            let supply_before = ctx.accounts.token_mint.supply;
+            ctx.accounts
+                .burn_config
+                .record_burn(burn_amount, supply_before, now)?;
+
+            if let Some(schedule) = fee_schedule.as_ref().filter(|_| protocol_fee > 0) {
+                transfer_protocol_fee(
+                    schedule,
+                    ctx.accounts.fee_token_account.as_ref(),
+                    &ctx.accounts.token_account,
+                    &ctx.accounts.token_mint,
+                    &ctx.accounts.executor,
+                    &ctx.accounts.token_program,
+                    protocol_fee,
+                )?;
+            }
+
+            if from_vault {
+                burn_from_vault(
+                    &ctx.accounts.burn_config,
+                    ctx.accounts.token_program.to_account_info(),
+                    &ctx.accounts.token_mint,
+                    ctx.accounts.token_account.to_account_info(),
+                    burn_amount,
+                )?;
+            } else {
+                let cpi_accounts = Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.token_account.to_account_info(),
+                    authority: ctx.accounts.executor.to_account_info(),
+                };
+                let cpi_ctx =
+                    CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+                burn_checked(cpi_ctx, burn_amount, ctx.accounts.token_mint.decimals)?;
+            }
+        }
+        let burned = amount - protocol_fee;
+
+        let rent_reclaimed = ctx.accounts.token_account.to_account_info().lamports();
+        if from_vault {
+            close_config_token_account(
+                &ctx.accounts.burn_config,
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.token_account.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+            )?;
+            ctx.accounts.burn_config.vault = None;
+        } else {
+            let cpi_accounts = CloseAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                destination: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.executor.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token_interface::close_account(cpi_ctx)?;
+        }
+
+        ctx.accounts.token_mint.reload()?;
+        let config = &ctx.accounts.burn_config;
+
+        msg!("🔥 Burned {} and closed {}", burned, ctx.accounts.token_account.key());
+        if protocol_fee > 0 {
+            msg!("   Protocol fee: {}", protocol_fee);
+        }
+        msg!("   Rent reclaimed: {} lamports to {}", rent_reclaimed, ctx.accounts.destination.key());
+        if let Some(decision) = &ai_decision {
+            msg!("   AI confidence: {}% ({:?})", decision.confidence, decision.sentiment);
+        }
+
+        emit!(TokenAccountClosed {
+            burn_config: config.key(),
+            token_account: ctx.accounts.token_account.key(),
+            executor,
+            burned,
+            protocol_fee,
+            rent_reclaimed,
+            destination: ctx.accounts.destination.key(),
+            was_vault: from_vault,
+            total_burned: config.total_burned,
+            supply_after: ctx.accounts.token_mint.supply,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Buy tokens through an allow-listed swap program and burn them
+    ///
+    /// Replaces the separate Jupiter swap and burn in the agent flow, so the
+    /// bought tokens never sit in a hot wallet. The swap program is invoked
+    /// with `swap_data` and the route accounts passed as `remaining_accounts`
+    /// (the executor's signature carries through; the config PDA never signs).
+    /// Whatever lands in `token_account` is burned in the same instruction,
+    /// under the config's pause switches and caps.
+    ///
+    /// Like `execute_autonomous_burn`, the burn pays the x402 fee per the
+    /// config's `payment_mode` and, outside the burn vault, the protocol fee
+    /// (taken from the tokens received). It needs an `AiDecision` whose
+    /// `max_amount` covers the amount received when the config has an
+    /// `ai_signer`, and the guardian quorum when that amount reaches
+    /// `quorum_burn_threshold`. Guardians sign as extra remaining accounts;
+    /// they are left out of the swap route, so the swap program never sees
+    /// their signatures.
+    ///
+    /// # Arguments
+    /// * `min_amount_out` - Fewest tokens the swap must deliver to `token_account`
+    /// * `swap_data` - Instruction data for the swap program
+    /// * `x402_signature` - Payment verification signature from x402 service
+    pub fn buyback_and_burn<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuybackAndBurn<'info>>,
+        min_amount_out: u64,
+        swap_data: Vec<u8>,
+        x402_signature: String,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.burn_config;
+        config.check_active(&ctx.accounts.global_config)?;
+
+        let swap_program = ctx.accounts.swap_program.key();
+        require!(
+            config.swap_programs.contains(&swap_program),
+            ErrorCode::SwapProgramNotAllowed
+        );
+        require!(min_amount_out > 0, ErrorCode::InvalidMinAmountOut);
+
+        let executor = ctx.accounts.executor.key();
+        if executor != config.authority {
+            ctx.accounts
+                .burn_operator
+                .as_ref()
+                .ok_or(ErrorCode::UnauthorizedExecutor)?
+                .authorize(BurnOperator::PERMISSION_BURN, now)?;
+        }
+        let from_vault = config.vault == Some(ctx.accounts.token_account.key());
+        if !from_vault {
+            require_keys_eq!(
+                ctx.accounts.token_account.owner,
+                executor,
+                ErrorCode::InvalidBurnSource
+            );
+        }
+
+        // Swap into the token account and measure what actually arrived
+        let balance_before = ctx.accounts.token_account.amount;
+        let route: Vec<AccountInfo<'info>> = ctx
+            .remaining_accounts
+            .iter()
+            .filter(|account| !(account.is_signer && config.guardians.contains(account.key)))
+            .cloned()
+            .collect();
+        let swap_ix = Instruction {
+            program_id: swap_program,
+            accounts: route
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: swap_data,
+        };
+        let mut swap_accounts = route;
+        swap_accounts.push(ctx.accounts.swap_program.to_account_info());
+        invoke(&swap_ix, &swap_accounts)?;
+
+        ctx.accounts.token_account.reload()?;
+        ctx.accounts.token_mint.reload()?;
+        let received = ctx
+            .accounts
+            .token_account
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(ErrorCode::SlippageExceeded)?;
+        require!(received >= min_amount_out, ErrorCode::SlippageExceeded);
+        require!(
+            received >= ctx.accounts.burn_config.min_burn_amount,
+            ErrorCode::BelowMinBurnAmount
+        );
+
+        // The registered AI signer must approve this specific burn, at this size
+        let ai_decision = load_ai_decision(
+            &ctx.accounts.burn_config,
+            &ctx.accounts.instructions.to_account_info(),
+            received,
+        )?;
+
+        // Large burns also need M-of-N guardian signatures
+        if ctx.accounts.burn_config.requires_quorum(received) {
+            ctx.accounts.burn_config.check_quorum(ctx.remaining_accounts)?;
+        }
+        if ai_decision.is_some() {
+            ctx.accounts.burn_config.ai_decision_nonce += 1;
+        }
+
+        if let Some(operator) = ctx.accounts.burn_operator.as_mut() {
+            if executor != ctx.accounts.burn_config.authority {
+                operator.allowance = operator
+                    .allowance
+                    .checked_sub(received)
+                    .ok_or(ErrorCode::OperatorAllowanceExceeded)?;
+            }
+        }
+
+        // Same x402 payment and protocol fee as any other burn
+        let exempt = from_vault || ctx.accounts.fee_exemption.is_some();
+        let fee_schedule = load_if_initialized::<FeeSchedule>(&ctx.accounts.fee_schedule)?;
+        let protocol_fee = fee_schedule.as_ref().map_or(0, |schedule| {
+            schedule.fee_for(received, ctx.accounts.burn_config.burn_count, exempt)
+        });
+        let burn_amount = received - protocol_fee;
+
+        let paid = ctx.accounts.take_x402_payment()?;
+        msg!("🔒 x402 Payment Verified: {} ({} paid)", x402_signature, paid);
+
+        let burn_config_key = ctx.accounts.burn_config.key();
+        if let Some(receipt) = ctx.accounts.payment_receipt.as_mut() {
+            receipt.payer = ctx.accounts.payer.key();
+            receipt.amount = paid;
+            receipt.burn_config = burn_config_key;
+            receipt.slot = Clock::get()?.slot;
+            receipt.bump = ctx.bumps.payment_receipt;
+        }
+        let config = &mut ctx.accounts.burn_config;
+        if config.payment_mode == PaymentMode::PrepaidCredits {
+            config.credit_fees_accrued = config.credit_fees_accrued.checked_add(paid).unwrap();
+        }
+
+        let supply_before = ctx.accounts.token_mint.supply;
+        ctx.accounts
+            .burn_config
+            .record_burn(burn_amount, supply_before, now)?;
+
+        if let Some(schedule) = fee_schedule.as_ref().filter(|_| protocol_fee > 0) {
+            transfer_protocol_fee(
+                schedule,
+                ctx.accounts.fee_token_account.as_ref(),
+                &ctx.accounts.token_account,
+                &ctx.accounts.token_mint,
+                &ctx.accounts.executor,
+                &ctx.accounts.token_program,
+                protocol_fee,
+            )?;
+        }
+
+        if from_vault {
+            burn_from_vault(
+                &ctx.accounts.burn_config,
+                ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.token_mint,
+                ctx.accounts.token_account.to_account_info(),
+                burn_amount,
+            )?;
+        } else {
+            let cpi_accounts = Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.executor.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            burn_checked(cpi_ctx, burn_amount, ctx.accounts.token_mint.decimals)?;
+        }
+
+        ctx.accounts.token_mint.reload()?;
+        let config = &ctx.accounts.burn_config;
+
+        msg!("🔥 Buyback via {}: bought {}, burned {}", swap_program, received, burn_amount);
+        msg!("   Minimum out: {}", min_amount_out);
+        if protocol_fee > 0 {
+            msg!("   Protocol fee: {}", protocol_fee);
+        }
+        if let Some(decision) = &ai_decision {
+            msg!("   AI confidence: {}% ({:?})", decision.confidence, decision.sentiment);
+        }
+
+        emit!(BuybackBurnEvent {
+            burn_config: config.key(),
+            executor,
+            swap_program,
+            min_amount_out,
+            received,
+            protocol_fee,
+            total_burned: config.total_burned,
+            supply_before,
+            supply_after: ctx.accounts.token_mint.supply,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
     /// Update burn configuration
+    #[allow(clippy::too_many_arguments)]
     pub fn update_burn_config(
         ctx: Context<UpdateBurnConfig>,
         new_profit_threshold: Option<u64>,
         new_burn_percentage: Option<u16>,
         new_min_burn_amount: Option<u64>,
+        new_payment_amount: Option<u64>,
+        new_profit_oracle: Option<Pubkey>,
+        new_auto_pause_bps: Option<u16>,
+        new_payment_mode: Option<PaymentMode>,
+        new_profit_mode: Option<ProfitMode>,
+        new_drawdown_reset_bps: Option<u16>,
+        new_profit_source: Option<ProfitSource>,
     ) -> Result<()> {
         let config = &mut ctx.accounts.burn_config;
 
@@ -139,91 +1012,3739 @@ pub mod gigabrain_burn {
             msg!("Updated min burn amount: {}", min_amount);
         }
 
+        if let Some(payment_amount) = new_payment_amount {
+            config.payment_amount = payment_amount;
+            msg!("Updated x402 payment amount: {}", payment_amount);
+        }
+
+        if let Some(oracle) = new_profit_oracle {
+            config.profit_oracle = oracle;
+            msg!("Updated profit oracle: {}", oracle);
+        }
+
+        if let Some(auto_pause_bps) = new_auto_pause_bps {
+            require!(auto_pause_bps <= 10000, ErrorCode::InvalidBurnPercentage);
+            config.auto_pause_bps = auto_pause_bps;
+            msg!("Updated auto-pause share: {}%", auto_pause_bps as f64 / 100.0);
+        }
+
+        if let Some(payment_mode) = new_payment_mode {
+            config.payment_mode = payment_mode;
+            msg!("Updated x402 payment mode: {:?}", payment_mode);
+        }
+
+        if let Some(profit_mode) = new_profit_mode {
+            config.profit_mode = profit_mode;
+            msg!("Updated profit mode: {:?}", profit_mode);
+        }
+
+        if let Some(drawdown_reset_bps) = new_drawdown_reset_bps {
+            require!(drawdown_reset_bps <= 10000, ErrorCode::InvalidBurnPercentage);
+            config.drawdown_reset_bps = drawdown_reset_bps;
+            msg!("Updated drawdown reset: {}%", drawdown_reset_bps as f64 / 100.0);
+        }
+
+        if let Some(profit_source) = new_profit_source {
+            config.profit_source = profit_source;
+            msg!("Updated profit source: {:?}", profit_source);
+        }
+
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct InitializeBurnConfig<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + BurnConfig::INIT_SPACE,
-        seeds = [b"burn_config", token_mint.key().as_ref()],
-        bump
-    )]
-    pub burn_config: Account<'info, BurnConfig>,
-    
-    pub token_mint: Account<'info, Mint>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Pause or unpause burns for this config
+    pub fn set_paused(ctx: Context<UpdateBurnConfig>, paused: bool) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        config.paused = paused;
 
-#[derive(Accounts)]
-pub struct ExecuteAutonomousBurn<'info> {
-    #[account(
-        mut,
-        seeds = [b"burn_config", token_mint.key().as_ref()],
-        bump = burn_config.bump,
-        has_one = authority,
-        has_one = token_mint,
-    )]
-    pub burn_config: Account<'info, BurnConfig>,
-    
-    #[account(mut)]
-    pub token_mint: Account<'info, Mint>,
-    
-    #[account(
-        mut,
-        token::mint = token_mint,
-        token::authority = authority,
-    )]
-    pub token_account: Account<'info, TokenAccount>,
-    
-    pub authority: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
-}
+        emit_pause_change(Some(config.key()), ctx.accounts.authority.key(), paused)
+    }
 
-#[derive(Accounts)]
-pub struct UpdateBurnConfig<'info> {
-    #[account(
-        mut,
-        seeds = [b"burn_config", token_mint.key().as_ref()],
-        bump = burn_config.bump,
-        has_one = authority,
-    )]
-    pub burn_config: Account<'info, BurnConfig>,
-    
-    pub token_mint: Account<'info, Mint>,
-    
-    pub authority: Signer<'info>,
-}
+    /// Create the program-wide pause switch
+    ///
+    /// Only the program's upgrade authority, as recorded in its ProgramData
+    /// account, can create it and becomes its admin.
+    pub fn initialize_global_config(ctx: Context<InitializeGlobalConfig>) -> Result<()> {
+        let global = &mut ctx.accounts.global_config;
+        global.admin = ctx.accounts.admin.key();
+        global.paused = false;
+        global.bump = ctx.bumps.global_config;
 
-#[account]
-#[derive(InitSpace)]
-pub struct BurnConfig {
-    pub authority: Pubkey,
-    pub token_mint: Pubkey,
-    pub profit_threshold: u64,
-    pub burn_percentage: u16,
-    pub min_burn_amount: u64,
-    pub total_burned: u64,
-    pub burn_count: u64,
-    pub bump: u8,
-}
+        msg!("✅ Global config initialized, admin: {}", global.admin);
 
-#[event]
-pub struct BurnEvent {
-    pub authority: Pubkey,
-    pub token_mint: Pubkey,
-    pub amount: u64,
-    pub profit_amount: u64,
-    pub total_burned: u64,
-    pub burn_count: u64,
+        Ok(())
+    }
+
+    /// Pause or unpause burns for every config
+    ///
+    /// The signer must be the current upgrade authority; the stored admin is
+    /// refreshed so it follows upgrade-authority rotations.
+    pub fn set_global_paused(ctx: Context<SetGlobalPaused>, paused: bool) -> Result<()> {
+        let global = &mut ctx.accounts.global_config;
+        global.admin = ctx.accounts.admin.key();
+        global.paused = paused;
+
+        emit_pause_change(None, global.admin, paused)
+    }
+
+    /// Create the program-wide protocol fee schedule (upgrade authority only)
+    ///
+    /// # Arguments
+    /// * `fee_bps` - Share of each burn sent to `fee_destination` (basis points)
+    /// * `free_burns` - Burns per config that are fee-free
+    /// * `fee_destination` - Wallet whose token accounts receive the fee
+    pub fn initialize_fee_schedule(
+        ctx: Context<InitializeFeeSchedule>,
+        fee_bps: u16,
+        free_burns: u64,
+        fee_destination: Pubkey,
+    ) -> Result<()> {
+        let schedule = &mut ctx.accounts.fee_schedule;
+        schedule.bump = ctx.bumps.fee_schedule;
+        schedule.set(fee_bps, free_burns, fee_destination)
+    }
+
+    /// Replace the protocol fee schedule (upgrade authority only)
+    pub fn update_fee_schedule(
+        ctx: Context<UpdateFeeSchedule>,
+        fee_bps: u16,
+        free_burns: u64,
+        fee_destination: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .fee_schedule
+            .set(fee_bps, free_burns, fee_destination)
+    }
+
+    /// Exempt a config authority's burns from the protocol fee (upgrade authority only)
+    pub fn add_fee_exemption(ctx: Context<AddFeeExemption>, wallet: Pubkey) -> Result<()> {
+        let exemption = &mut ctx.accounts.fee_exemption;
+        exemption.wallet = wallet;
+        exemption.bump = ctx.bumps.fee_exemption;
+
+        msg!("✅ Fee exemption added: {}", wallet);
+
+        Ok(())
+    }
+
+    /// Remove a fee exemption (upgrade authority only)
+    pub fn remove_fee_exemption(ctx: Context<RemoveFeeExemption>) -> Result<()> {
+        msg!("✅ Fee exemption removed: {}", ctx.accounts.fee_exemption.wallet);
+
+        Ok(())
+    }
+
+    /// Set the on-chain burn caps
+    ///
+    /// # Arguments
+    /// * `max_burn_amount` - Largest single burn (0 = no cap)
+    /// * `window_seconds` - Length of the burn window, e.g. 86400 for 24h
+    /// * `window_max_burned` - Most tokens burned per window (0 = no cap)
+    /// * `min_burn_interval` - Minimum seconds between two burns
+    /// * `max_supply_bps` - Largest single burn as a share of mint supply (0 = no cap)
+    pub fn set_burn_limits(
+        ctx: Context<UpdateBurnConfig>,
+        max_burn_amount: u64,
+        window_seconds: i64,
+        window_max_burned: u64,
+        min_burn_interval: i64,
+        max_supply_bps: u16,
+    ) -> Result<()> {
+        require!(
+            window_seconds >= 0 && min_burn_interval >= 0,
+            ErrorCode::InvalidBurnLimits
+        );
+        require!(
+            window_max_burned == 0 || window_seconds > 0,
+            ErrorCode::InvalidBurnLimits
+        );
+        require!(max_supply_bps <= 10000, ErrorCode::InvalidBurnLimits);
+
+        let config = &mut ctx.accounts.burn_config;
+        config.max_burn_amount = max_burn_amount;
+        config.window_seconds = window_seconds;
+        config.window_max_burned = window_max_burned;
+        config.min_burn_interval = min_burn_interval;
+        config.max_supply_bps = max_supply_bps;
+
+        msg!("Updated burn limits");
+        msg!("   Max per burn: {}", max_burn_amount);
+        msg!("   Max per {}s window: {}", window_seconds, window_max_burned);
+        msg!("   Min interval: {}s", min_burn_interval);
+        msg!("   Max share of supply: {}%", max_supply_bps as f64 / 100.0);
+
+        Ok(())
+    }
+
+    /// Price profit through an oracle feed instead of treating it as tokens
+    ///
+    /// With a feed set, `profit_amount` is read as quote base units (e.g.
+    /// USDC or lamports) and converted into token base units at the feed
+    /// price before `burn_percentage` is applied.
+    ///
+    /// # Arguments
+    /// * `price_feed` - Pyth-compatible price account quoting the token, or `None` to disable
+    /// * `quote_decimals` - Decimals of the quote asset profit is measured in
+    /// * `max_price_age` - Oldest accepted price, in seconds
+    /// * `max_price_confidence_bps` - Widest accepted confidence interval, relative to price
+    pub fn set_price_feed(
+        ctx: Context<UpdateBurnConfig>,
+        price_feed: Option<Pubkey>,
+        quote_decimals: u8,
+        max_price_age: i64,
+        max_price_confidence_bps: u16,
+    ) -> Result<()> {
+        require!(
+            price_feed.is_none() || max_price_age > 0,
+            ErrorCode::InvalidPriceFeedSettings
+        );
+        require!(
+            max_price_confidence_bps <= 10000,
+            ErrorCode::InvalidPriceFeedSettings
+        );
+
+        let config = &mut ctx.accounts.burn_config;
+        config.price_feed = price_feed;
+        config.quote_decimals = quote_decimals;
+        config.max_price_age = max_price_age;
+        config.max_price_confidence_bps = max_price_confidence_bps;
+
+        match price_feed {
+            Some(feed) => {
+                msg!("Updated price feed: {}", feed);
+                msg!("   Max age: {}s", max_price_age);
+                msg!("   Max confidence: {}%", max_price_confidence_bps as f64 / 100.0);
+            }
+            None => msg!("Price feed disabled"),
+        }
+
+        Ok(())
+    }
+
+    /// Require an AI decision signed by `ai_signer` for every burn
+    ///
+    /// # Arguments
+    /// * `ai_signer` - Key whose Ed25519 signature attests `AiDecision`s, or `None` to disable
+    /// * `min_ai_confidence` - Lowest accepted confidence (0-100)
+    /// * `require_positive_sentiment` - Reject decisions whose sentiment is not positive
+    pub fn set_ai_policy(
+        ctx: Context<UpdateBurnConfig>,
+        ai_signer: Option<Pubkey>,
+        min_ai_confidence: u8,
+        require_positive_sentiment: bool,
+    ) -> Result<()> {
+        require!(min_ai_confidence <= 100, ErrorCode::InvalidAiPolicy);
+
+        let config = &mut ctx.accounts.burn_config;
+        config.ai_signer = ai_signer;
+        config.min_ai_confidence = min_ai_confidence;
+        config.require_positive_sentiment = require_positive_sentiment;
+
+        match ai_signer {
+            Some(signer) => {
+                msg!("Updated AI signer: {}", signer);
+                msg!("   Min confidence: {}%", min_ai_confidence);
+                msg!("   Require positive sentiment: {}", require_positive_sentiment);
+            }
+            None => msg!("AI decision requirement disabled"),
+        }
+
+        Ok(())
+    }
+
+    /// Create the config's program-owned burn vault
+    ///
+    /// The vault is a token account at `["burn_vault", burn_config]` whose
+    /// owner is the burn config PDA. Anyone can fund it with a plain SPL
+    /// transfer; tokens only ever leave it through policy-checked burns.
+    pub fn initialize_burn_vault(ctx: Context<InitializeBurnVault>) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        config.vault = Some(ctx.accounts.vault.key());
+
+        msg!("✅ Burn vault initialized: {}", ctx.accounts.vault.key());
+
+        Ok(())
+    }
+
+    /// Create the config's credit vault holding prepaid x402 credits
+    ///
+    /// The vault is a payment-mint token account at `["credit_vault",
+    /// burn_config]` owned by the burn config PDA.
+    pub fn initialize_credit_vault(ctx: Context<InitializeCreditVault>) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        config.credit_vault = Some(ctx.accounts.credit_vault.key());
+
+        msg!("✅ Credit vault initialized: {}", ctx.accounts.credit_vault.key());
+
+        Ok(())
+    }
+
+    /// Open a prepaid credit account for the signer on this config
+    ///
+    /// # Arguments
+    /// * `low_balance_threshold` - Emit `LowCreditBalance` once a burn leaves
+    ///   the balance below this amount
+    pub fn open_credit_account(
+        ctx: Context<OpenCreditAccount>,
+        low_balance_threshold: u64,
+    ) -> Result<()> {
+        let credit_account = &mut ctx.accounts.credit_account;
+        credit_account.burn_config = ctx.accounts.burn_config.key();
+        credit_account.owner = ctx.accounts.owner.key();
+        credit_account.balance = 0;
+        credit_account.low_balance_threshold = low_balance_threshold;
+        credit_account.bump = ctx.bumps.credit_account;
+
+        msg!("✅ Credit account opened for {}", credit_account.owner);
+
+        Ok(())
+    }
+
+    /// Top up a credit account with payment-mint tokens (anyone may deposit)
+    pub fn deposit_credits(ctx: Context<DepositCredits>, amount: u64) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.source.to_account_info(),
+            mint: ctx.accounts.payment_mint.to_account_info(),
+            to: ctx.accounts.credit_vault.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.payment_mint.decimals)?;
+
+        let credit_account = &mut ctx.accounts.credit_account;
+        credit_account.balance = credit_account.balance.checked_add(amount).unwrap();
+
+        msg!("💳 Deposited {} credits, balance: {}", amount, credit_account.balance);
+
+        emit!(CreditsDeposited {
+            credit_account: credit_account.key(),
+            depositor: ctx.accounts.depositor.key(),
+            amount,
+            balance: credit_account.balance,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw unused credits back to the credit account owner
+    pub fn withdraw_credits(ctx: Context<WithdrawCredits>, amount: u64) -> Result<()> {
+        let credit_account = &mut ctx.accounts.credit_account;
+        credit_account.balance = credit_account
+            .balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientCredits)?;
+        let balance = credit_account.balance;
+
+        transfer_from_config_account(
+            &ctx.accounts.burn_config,
+            ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.payment_mint,
+            ctx.accounts.credit_vault.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            amount,
+        )?;
+
+        msg!("💳 Withdrew {} credits, balance: {}", amount, balance);
+
+        emit!(CreditsWithdrawn {
+            credit_account: ctx.accounts.credit_account.key(),
+            amount,
+            balance,
+        });
+
+        Ok(())
+    }
+
+    /// Move credits spent on burns from the credit vault to the payment
+    /// treasury. Permissionless: the destination is fixed by the config.
+    pub fn sweep_credit_fees(ctx: Context<SweepCreditFees>) -> Result<()> {
+        let amount = ctx.accounts.burn_config.credit_fees_accrued;
+
+        transfer_from_config_account(
+            &ctx.accounts.burn_config,
+            ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.payment_mint,
+            ctx.accounts.credit_vault.to_account_info(),
+            ctx.accounts.payment_treasury.to_account_info(),
+            amount,
+        )?;
+        ctx.accounts.burn_config.credit_fees_accrued = 0;
+
+        msg!("💳 Swept {} credit fees to treasury", amount);
+
+        Ok(())
+    }
+
+    /// Update the limited set of burn parameters an operator may tune
+    ///
+    /// Requires a `BurnOperator` with the update-limited permission. Only the
+    /// profit threshold and minimum burn amount can be changed this way.
+    pub fn operator_update_burn_config(
+        ctx: Context<OperatorUpdateBurnConfig>,
+        new_profit_threshold: Option<u64>,
+        new_min_burn_amount: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.burn_operator.authorize(
+            BurnOperator::PERMISSION_UPDATE_LIMITED,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        let config = &mut ctx.accounts.burn_config;
+
+        if let Some(threshold) = new_profit_threshold {
+            config.profit_threshold = threshold;
+            msg!("Operator updated profit threshold: {}", threshold);
+        }
+
+        if let Some(min_amount) = new_min_burn_amount {
+            config.min_burn_amount = min_amount;
+            msg!("Operator updated min burn amount: {}", min_amount);
+        }
+
+        Ok(())
+    }
+
+    /// Register a burn operator (e.g. an AI agent hot key)
+    ///
+    /// # Arguments
+    /// * `operator` - Key allowed to act on this config
+    /// * `allowance` - Total tokens the operator may burn before being re-added
+    /// * `expires_at` - Unix timestamp after which the operator is inactive
+    /// * `permissions` - Bitmask of `BurnOperator::PERMISSION_*` flags
+    pub fn add_burn_operator(
+        ctx: Context<AddBurnOperator>,
+        operator: Pubkey,
+        allowance: u64,
+        expires_at: i64,
+        permissions: u8,
+    ) -> Result<()> {
+        require!(
+            permissions & !BurnOperator::PERMISSION_ALL == 0,
+            ErrorCode::InvalidOperatorPermissions
+        );
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            ErrorCode::OperatorExpired
+        );
+
+        let burn_operator = &mut ctx.accounts.burn_operator;
+        burn_operator.burn_config = ctx.accounts.burn_config.key();
+        burn_operator.operator = operator;
+        burn_operator.allowance = allowance;
+        burn_operator.expires_at = expires_at;
+        burn_operator.permissions = permissions;
+        burn_operator.bump = ctx.bumps.burn_operator;
+
+        msg!("Added burn operator: {}", operator);
+        msg!("   Allowance: {}", allowance);
+        msg!("   Expires at: {}", expires_at);
+        msg!("   Permissions: {:#04b}", permissions);
+
+        emit!(OperatorAdded {
+            burn_config: burn_operator.burn_config,
+            operator,
+            allowance,
+            expires_at,
+            permissions,
+        });
+
+        Ok(())
+    }
+
+    /// Remove a burn operator, returning its rent to the authority
+    pub fn remove_burn_operator(ctx: Context<RemoveBurnOperator>) -> Result<()> {
+        let burn_operator = &ctx.accounts.burn_operator;
+
+        msg!("Removed burn operator: {}", burn_operator.operator);
+
+        emit!(OperatorRemoved {
+            burn_config: burn_operator.burn_config,
+            operator: burn_operator.operator,
+        });
+
+        Ok(())
+    }
+
+    /// Attach a recurring burn schedule to the config
+    ///
+    /// Every `interval_seconds` from `start_time`, anyone may call
+    /// `crank_scheduled_burn` to burn `tick_amount` from the config's burn
+    /// vault and collect `crank_reward` lamports from the schedule's bounty.
+    /// Cranks carry no guardian signatures, so a fixed tick may not reach the
+    /// config's `quorum_burn_threshold`.
+    ///
+    /// # Arguments
+    /// * `tick_amount` - Fixed amount or share of the vault balance burned per tick
+    /// * `interval_seconds` - Time between ticks
+    /// * `start_time` - Unix timestamp of the first tick
+    /// * `end_time` - No ticks at or after this timestamp, if set
+    /// * `crank_reward` - Lamports paid to the caller per tick
+    pub fn create_burn_schedule(
+        ctx: Context<CreateBurnSchedule>,
+        tick_amount: TickAmount,
+        interval_seconds: i64,
+        start_time: i64,
+        end_time: Option<i64>,
+        crank_reward: u64,
+    ) -> Result<()> {
+        require!(ctx.accounts.burn_config.vault.is_some(), ErrorCode::BurnVaultMissing);
+        require!(interval_seconds > 0, ErrorCode::InvalidBurnSchedule);
+        require!(
+            !matches!(end_time, Some(end) if end <= start_time),
+            ErrorCode::InvalidBurnSchedule
+        );
+        match tick_amount {
+            TickAmount::Fixed { amount } => {
+                require!(amount > 0, ErrorCode::InvalidBurnSchedule);
+                require!(
+                    !ctx.accounts.burn_config.requires_quorum(amount),
+                    ErrorCode::ScheduledBurnNeedsQuorum
+                );
+            }
+            TickAmount::BalanceBps { bps } => {
+                require!(bps > 0 && bps <= 10000, ErrorCode::InvalidBurnSchedule)
+            }
+        }
+
+        let schedule = &mut ctx.accounts.burn_schedule;
+        schedule.burn_config = ctx.accounts.burn_config.key();
+        schedule.tick_amount = tick_amount;
+        schedule.interval_seconds = interval_seconds;
+        schedule.next_tick_at = start_time;
+        schedule.end_time = end_time;
+        schedule.crank_reward = crank_reward;
+        schedule.ticks = 0;
+        schedule.bump = ctx.bumps.burn_schedule;
+
+        msg!("✅ Burn schedule created: {:?} every {}s", tick_amount, interval_seconds);
+
+        Ok(())
+    }
+
+    /// Add lamports to a schedule's crank bounty (anyone may fund)
+    pub fn fund_burn_schedule(ctx: Context<FundBurnSchedule>, lamports: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.burn_schedule.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
+
+        msg!("💰 Burn schedule bounty funded with {} lamports", lamports);
+
+        Ok(())
+    }
+
+    /// Execute a due scheduled burn from the burn vault (permissionless)
+    ///
+    /// Burns go through the same pause switches, caps and auto-pause as
+    /// `execute_autonomous_burn`, except that a tick large enough to need the
+    /// guardian quorum is rejected. The caller receives `crank_reward`
+    /// lamports, or whatever is left of the bounty above rent.
+    pub fn crank_scheduled_burn(ctx: Context<CrankScheduledBurn>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.burn_config;
+        config.check_active(&ctx.accounts.global_config)?;
+
+        let schedule = &ctx.accounts.burn_schedule;
+        require!(now >= schedule.next_tick_at, ErrorCode::ScheduleTickNotDue);
+        require!(
+            !matches!(schedule.end_time, Some(end) if schedule.next_tick_at >= end),
+            ErrorCode::ScheduleEnded
+        );
+
+        let source_balance = ctx.accounts.vault.amount;
+        let amount = schedule.tick_amount.resolve(source_balance);
+        require!(amount > 0, ErrorCode::BelowMinBurnAmount);
+        require!(amount >= config.min_burn_amount, ErrorCode::BelowMinBurnAmount);
+        require!(!config.requires_quorum(amount), ErrorCode::ScheduledBurnNeedsQuorum);
+
+        let supply_before = ctx.accounts.token_mint.supply;
+        ctx.accounts
+            .burn_config
+            .record_burn(amount, supply_before, now)?;
+
+        burn_from_vault(
+            &ctx.accounts.burn_config,
+            ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.token_mint,
+            ctx.accounts.vault.to_account_info(),
+            amount,
+        )?;
+
+        ctx.accounts.token_mint.reload()?;
+        let supply_after = ctx.accounts.token_mint.supply;
+
+        ctx.accounts.burn_schedule.advance(now);
+        let reward = pay_crank_reward(
+            &ctx.accounts.burn_schedule,
+            &ctx.accounts.cranker.to_account_info(),
+        )?;
+        let schedule = &ctx.accounts.burn_schedule;
+
+        let config = &mut ctx.accounts.burn_config;
+        if config.should_auto_pause(amount, source_balance) {
+            config.paused = true;
+            msg!("⚠️ Auto-pause tripped: burn of {} out of {} balance", amount, source_balance);
+            emit!(BurnsPaused {
+                burn_config: Some(config.key()),
+                by: ctx.accounts.cranker.key(),
+                auto_tripped: true,
+                timestamp: now,
+            });
+        }
+
+        msg!("⏰ Scheduled burn #{} executed: {}", schedule.ticks, amount);
+        msg!("   Crank reward: {} lamports", reward);
+        msg!("   Next tick at: {}", schedule.next_tick_at);
+
+        emit!(ScheduledBurnCranked {
+            burn_config: config.key(),
+            cranker: ctx.accounts.cranker.key(),
+            amount,
+            reward,
+            tick: schedule.ticks,
+            next_tick_at: schedule.next_tick_at,
+            total_burned: config.total_burned,
+            supply_before,
+            supply_after,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Remove the schedule, refunding its rent and bounty to the authority
+    pub fn close_burn_schedule(_ctx: Context<CloseBurnSchedule>) -> Result<()> {
+        msg!("✅ Burn schedule closed");
+
+        Ok(())
+    }
+
+    /// Escrow a large burn and split it into time-spaced tranches
+    ///
+    /// `total_amount` moves from `source` into an escrow at
+    /// `["burn_order_escrow", burn_order]` owned by the config PDA. Tranche
+    /// `i` becomes executable at `start_time + i * tranche_interval`; the last
+    /// tranche also burns the division remainder. Tranches are sized from
+    /// what actually reached escrow (after any Token-2022 transfer fee), and
+    /// each must meet the config's `min_burn_amount`, here and when cranked. An order of
+    /// `quorum_burn_threshold` or more needs the guardian quorum here, as
+    /// signer remaining accounts, since its tranches are cranked unattended.
+    ///
+    /// # Arguments
+    /// * `order_id` - Caller-chosen id distinguishing this config's orders
+    /// * `total_amount` - Tokens to burn over the whole order
+    /// * `tranche_count` - Number of tranches
+    /// * `start_time` - Unix timestamp of the first tranche
+    /// * `tranche_interval` - Seconds between tranches (at least one)
+    pub fn create_burn_order(
+        ctx: Context<CreateBurnOrder>,
+        order_id: u64,
+        total_amount: u64,
+        tranche_count: u16,
+        start_time: i64,
+        tranche_interval: i64,
+    ) -> Result<()> {
+        require!(
+            total_amount > 0 && tranche_count > 0 && tranche_interval > 0,
+            ErrorCode::InvalidBurnOrder
+        );
+        let config = &ctx.accounts.burn_config;
+        if config.requires_quorum(total_amount) {
+            config.check_quorum(ctx.remaining_accounts)?;
+        }
+
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.source.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.escrow.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, total_amount, ctx.accounts.token_mint.decimals)?;
+
+        // Token-2022 transfer fees can leave less in escrow than was sent
+        ctx.accounts.escrow.reload()?;
+        let escrowed = ctx.accounts.escrow.amount;
+        // Every tranche must burn something, and at least the config minimum
+        let tranche_amount = escrowed / tranche_count as u64;
+        require!(tranche_amount > 0, ErrorCode::InvalidBurnOrder);
+        require!(
+            tranche_amount >= ctx.accounts.burn_config.min_burn_amount,
+            ErrorCode::BelowMinBurnAmount
+        );
+
+        let order = &mut ctx.accounts.burn_order;
+        order.burn_config = ctx.accounts.burn_config.key();
+        order.order_id = order_id;
+        order.escrow = ctx.accounts.escrow.key();
+        order.total_amount = escrowed;
+        order.burned = 0;
+        order.tranche_count = tranche_count;
+        order.tranches_executed = 0;
+        order.start_time = start_time;
+        order.tranche_interval = tranche_interval;
+        order.bump = ctx.bumps.burn_order;
+
+        msg!("✅ Burn order #{} created: {} in {} tranches", order_id, escrowed, tranche_count);
+
+        emit!(BurnOrderProgress {
+            burn_order: order.key(),
+            tranches_executed: 0,
+            tranche_count,
+            tranche_amount: 0,
+            burned: 0,
+            remaining: escrowed,
+            next_tranche_at: Some(start_time),
+        });
+
+        Ok(())
+    }
+
+    /// Burn the next due tranche of an order (permissionless)
+    pub fn crank_burn_order(ctx: Context<CrankBurnOrder>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts
+            .burn_config
+            .check_active(&ctx.accounts.global_config)?;
+
+        let order = &ctx.accounts.burn_order;
+        let due_at = order
+            .tranche_due_at(order.tranches_executed)
+            .ok_or(ErrorCode::BurnOrderComplete)?;
+        require!(now >= due_at, ErrorCode::TrancheNotDue);
+        let amount = order.next_tranche_amount();
+        require!(
+            amount >= ctx.accounts.burn_config.min_burn_amount,
+            ErrorCode::BelowMinBurnAmount
+        );
+
+        let supply_before = ctx.accounts.token_mint.supply;
+        ctx.accounts
+            .burn_config
+            .record_burn(amount, supply_before, now)?;
+
+        burn_from_vault(
+            &ctx.accounts.burn_config,
+            ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.token_mint,
+            ctx.accounts.escrow.to_account_info(),
+            amount,
+        )?;
+
+        let order = &mut ctx.accounts.burn_order;
+        order.tranches_executed += 1;
+        order.burned += amount;
+        let remaining = order.total_amount - order.burned;
+
+        msg!(
+            "🔥 Burn order #{} tranche {}/{}: {}",
+            order.order_id,
+            order.tranches_executed,
+            order.tranche_count,
+            amount
+        );
+
+        emit!(BurnOrderProgress {
+            burn_order: order.key(),
+            tranches_executed: order.tranches_executed,
+            tranche_count: order.tranche_count,
+            tranche_amount: amount,
+            burned: order.burned,
+            remaining,
+            next_tranche_at: order.tranche_due_at(order.tranches_executed),
+        });
+
+        Ok(())
+    }
+
+    /// Cancel or close out an order, releasing any unburned tokens
+    ///
+    /// The escrow remainder goes to `destination`; escrow and order rent go
+    /// back to the authority.
+    pub fn cancel_burn_order(ctx: Context<CancelBurnOrder>) -> Result<()> {
+        let released = ctx.accounts.escrow.amount;
+        if released > 0 {
+            transfer_from_config_account(
+                &ctx.accounts.burn_config,
+                ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.token_mint,
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                released,
+            )?;
+        }
+        close_config_token_account(
+            &ctx.accounts.burn_config,
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+        )?;
+
+        let order = &ctx.accounts.burn_order;
+        msg!("✅ Burn order #{} closed, released {}", order.order_id, released);
+
+        emit!(BurnOrderCancelled {
+            burn_order: order.key(),
+            tranches_executed: order.tranches_executed,
+            burned: order.burned,
+            released,
+        });
+
+        Ok(())
+    }
+
+    /// Add a guardian to the config's quorum roster
+    pub fn add_guardian(ctx: Context<UpdateBurnConfig>, guardian: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        require!(
+            !config.guardians.contains(&guardian),
+            ErrorCode::GuardianAlreadyAdded
+        );
+        require!(
+            config.guardians.len() < BurnConfig::MAX_GUARDIANS,
+            ErrorCode::TooManyGuardians
+        );
+        config.guardians.push(guardian);
+
+        msg!("✅ Guardian added: {} ({} total)", guardian, config.guardians.len());
+
+        emit!(GuardianAdded {
+            burn_config: config.key(),
+            guardian,
+        });
+
+        Ok(())
+    }
+
+    /// Remove a guardian; the quorum must still be reachable afterwards
+    pub fn remove_guardian(ctx: Context<UpdateBurnConfig>, guardian: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        let index = config
+            .guardians
+            .iter()
+            .position(|key| *key == guardian)
+            .ok_or(ErrorCode::GuardianNotFound)?;
+        config.guardians.remove(index);
+        require!(
+            config.guardian_quorum as usize <= config.guardians.len(),
+            ErrorCode::InvalidQuorum
+        );
+
+        msg!("✅ Guardian removed: {} ({} left)", guardian, config.guardians.len());
+
+        emit!(GuardianRemoved {
+            burn_config: config.key(),
+            guardian,
+        });
+
+        Ok(())
+    }
+
+    /// Set the guardian quorum and the burn size that requires it
+    ///
+    /// # Arguments
+    /// * `guardian_quorum` - Guardian signatures required (M of the roster's N)
+    /// * `quorum_burn_threshold` - Smallest burn that needs the quorum (0 = never)
+    pub fn set_guardian_quorum(
+        ctx: Context<UpdateBurnConfig>,
+        guardian_quorum: u8,
+        quorum_burn_threshold: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        require!(
+            guardian_quorum as usize <= config.guardians.len(),
+            ErrorCode::InvalidQuorum
+        );
+        require!(
+            quorum_burn_threshold == 0 || guardian_quorum > 0,
+            ErrorCode::InvalidQuorum
+        );
+        config.guardian_quorum = guardian_quorum;
+        config.quorum_burn_threshold = quorum_burn_threshold;
+
+        msg!(
+            "Updated guardian quorum: {} of {} for burns of {} or more",
+            guardian_quorum,
+            config.guardians.len(),
+            quorum_burn_threshold
+        );
+
+        Ok(())
+    }
+
+    /// Allow `buyback_and_burn` to swap through `swap_program`
+    pub fn add_swap_program(ctx: Context<UpdateBurnConfig>, swap_program: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        require_keys_neq!(swap_program, crate::ID, ErrorCode::SwapProgramNotAllowed);
+        require!(
+            !config.swap_programs.contains(&swap_program),
+            ErrorCode::SwapProgramAlreadyAdded
+        );
+        require!(
+            config.swap_programs.len() < BurnConfig::MAX_SWAP_PROGRAMS,
+            ErrorCode::TooManySwapPrograms
+        );
+        config.swap_programs.push(swap_program);
+
+        msg!("✅ Swap program allowed: {}", swap_program);
+
+        emit!(SwapProgramAdded {
+            burn_config: config.key(),
+            swap_program,
+        });
+
+        Ok(())
+    }
+
+    /// Take `swap_program` off the buyback allowlist
+    pub fn remove_swap_program(ctx: Context<UpdateBurnConfig>, swap_program: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        let index = config
+            .swap_programs
+            .iter()
+            .position(|key| *key == swap_program)
+            .ok_or(ErrorCode::SwapProgramNotFound)?;
+        config.swap_programs.remove(index);
+
+        msg!("✅ Swap program removed: {}", swap_program);
+
+        emit!(SwapProgramRemoved {
+            burn_config: config.key(),
+            swap_program,
+        });
+
+        Ok(())
+    }
+
+    /// Propose a new authority for the burn config
+    ///
+    /// The handover only takes effect once `new_authority` calls
+    /// `accept_authority`. Proposing again replaces any pending proposal.
+    pub fn propose_authority(ctx: Context<UpdateBurnConfig>, new_authority: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        config.pending_authority = Some(new_authority);
+
+        msg!("Proposed authority transfer to: {}", new_authority);
+
+        emit!(AuthorityTransferProposed {
+            burn_config: config.key(),
+            authority: config.authority,
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer (signed by the proposed authority)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        let previous_authority = config.authority;
+        config.authority = ctx.accounts.pending_authority.key();
+        config.pending_authority = None;
+
+        msg!("Authority transferred: {} -> {}", previous_authority, config.authority);
+
+        emit!(AuthorityTransferAccepted {
+            burn_config: config.key(),
+            previous_authority,
+            new_authority: config.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a pending authority transfer
+    pub fn cancel_authority_transfer(ctx: Context<UpdateBurnConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.burn_config;
+        let pending_authority = config
+            .pending_authority
+            .take()
+            .ok_or(ErrorCode::NoPendingAuthority)?;
+
+        msg!("Cancelled authority transfer to: {}", pending_authority);
+
+        emit!(AuthorityTransferCancelled {
+            burn_config: config.key(),
+            authority: config.authority,
+            pending_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+/// Log and emit a manual pause state change; `burn_config` is `None` for the
+/// program-wide switch.
+fn emit_pause_change(burn_config: Option<Pubkey>, by: Pubkey, paused: bool) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let scope = burn_config.map_or("all configs".to_string(), |key| key.to_string());
+
+    if paused {
+        msg!("⏸️ Burns paused for {}", scope);
+        emit!(BurnsPaused {
+            burn_config,
+            by,
+            auto_tripped: false,
+            timestamp,
+        });
+    } else {
+        msg!("▶️ Burns unpaused for {}", scope);
+        emit!(BurnsUnpaused {
+            burn_config,
+            by,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+/// SPL Token / Token-2022 `TransferChecked` instruction tag
+const TRANSFER_CHECKED_TAG: u8 = 12;
+
+/// Verify that the instruction preceding the current one is an SPL Token (or
+/// Token-2022) `transfer_checked` paying the config's x402 fee, and return the amount paid.
+///
+/// Requiring the transfer to sit directly before the burn means a single
+/// payment can never be counted by two burn instructions in one transaction.
+/// The burn must itself be top-level: under CPI the instructions sysvar only
+/// shows the caller's instruction, so a wrapper program could run several
+/// burns behind one transfer.
+fn verify_x402_payment(
+    instructions: &AccountInfo,
+    config: &BurnConfig,
+    payer: &Pubkey,
+) -> Result<u64> {
+    require!(
+        get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT,
+        ErrorCode::PaymentRequiresTopLevel
+    );
+    let current_index = load_current_index_checked(instructions)? as usize;
+    require!(current_index > 0, ErrorCode::PaymentNotFound);
+
+    let ix = load_instruction_at_checked(current_index - 1, instructions)?;
+    require!(
+        ix.program_id == token::ID || ix.program_id == spl_token_2022::ID,
+        ErrorCode::PaymentNotFound
+    );
+    require!(
+        ix.data.len() >= 10 && ix.data[0] == TRANSFER_CHECKED_TAG,
+        ErrorCode::PaymentNotFound
+    );
+    // Accounts: [source, mint, destination, authority, ..multisig signers]
+    require!(ix.accounts.len() >= 4, ErrorCode::PaymentNotFound);
+    require_keys_eq!(ix.accounts[1].pubkey, config.payment_mint, ErrorCode::PaymentNotFound);
+    require_keys_eq!(ix.accounts[2].pubkey, config.payment_treasury, ErrorCode::PaymentNotFound);
+    require_keys_eq!(ix.accounts[3].pubkey, *payer, ErrorCode::PaymentNotFound);
+
+    let amount = u64::from_le_bytes(ix.data[1..9].try_into().unwrap());
+    require!(amount >= config.payment_amount, ErrorCode::InsufficientPayment);
+
+    Ok(amount)
+}
+
+/// Accounts through which the program collects the x402 fee itself
+/// (`PaymentMode::ProgramCollected`)
+struct X402PaymentAccounts<'a, 'info> {
+    source: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    treasury: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+}
+
+/// Verify or collect a burn's x402 fee per the config's `payment_mode`, and
+/// return the amount paid.
+///
+/// `VerifiedTransfer` needs the burn to create a payment receipt, so the same
+/// transfer can never pay twice; `PrepaidCredits` debits `credit_account`.
+fn take_x402_payment<'info>(
+    config: &BurnConfig,
+    payer: &Signer<'info>,
+    instructions: &AccountInfo<'info>,
+    has_receipt: bool,
+    payment: X402PaymentAccounts<'_, 'info>,
+    credit_account: Option<&mut Account<'info, CreditAccount>>,
+) -> Result<u64> {
+    match config.payment_mode {
+        PaymentMode::VerifiedTransfer => {
+            require!(has_receipt, ErrorCode::PaymentReceiptMissing);
+            verify_x402_payment(instructions, config, &payer.key())
+        }
+        PaymentMode::ProgramCollected => {
+            let (Some(source), Some(mint), Some(treasury), Some(token_program)) =
+                (payment.source, payment.mint, payment.treasury, payment.token_program)
+            else {
+                return err!(ErrorCode::PaymentAccountsMissing);
+            };
+            require_keys_eq!(mint.key(), config.payment_mint, ErrorCode::InvalidPaymentAccount);
+            require_keys_eq!(
+                treasury.key(),
+                config.payment_treasury,
+                ErrorCode::InvalidPaymentAccount
+            );
+
+            let cpi_accounts = TransferChecked {
+                from: source.to_account_info(),
+                mint: mint.to_account_info(),
+                to: treasury.to_account_info(),
+                authority: payer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            token_interface::transfer_checked(cpi_ctx, config.payment_amount, mint.decimals)?;
+
+            Ok(config.payment_amount)
+        }
+        PaymentMode::PrepaidCredits => {
+            credit_account
+                .ok_or(ErrorCode::CreditAccountMissing)?
+                .debit(config.payment_amount)?;
+            Ok(config.payment_amount)
+        }
+    }
+}
+
+/// Transfer a burn's protocol fee from `source` to the fee destination's
+/// account for the burned mint.
+fn transfer_protocol_fee<'info>(
+    schedule: &FeeSchedule,
+    fee_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    source: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    authority: &Signer<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    fee: u64,
+) -> Result<()> {
+    let destination = fee_token_account.ok_or(ErrorCode::FeeAccountMissing)?;
+    require_keys_eq!(
+        destination.owner,
+        schedule.fee_destination,
+        ErrorCode::InvalidFeeAccount
+    );
+    require_keys_eq!(destination.mint, mint.key(), ErrorCode::InvalidFeeAccount);
+
+    let cpi_accounts = TransferChecked {
+        from: source.to_account_info(),
+        mint: mint.to_account_info(),
+        to: destination.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, fee, mint.decimals)
+}
+
+/// `burn_checked` CPI that works against both SPL Token and Token-2022
+///
+/// anchor-spl 0.29 only exposes the unchecked `burn` through `token_interface`.
+fn burn_checked<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, Burn<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let ix = spl_token_2022::instruction::burn_checked(
+        ctx.program.key,
+        ctx.accounts.from.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    invoke_signed(
+        &ix,
+        &[ctx.accounts.from, ctx.accounts.mint, ctx.accounts.authority],
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Burn `amount` from a token account owned by the burn config PDA (the
+/// burn vault or an order escrow), signing as the config.
+fn burn_from_vault<'info>(
+    config: &Account<'info, BurnConfig>,
+    token_program: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = Burn {
+        mint: mint.to_account_info(),
+        from: vault,
+        authority: config.to_account_info(),
+    };
+
+    config.with_signer_seeds(|signer| {
+        let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
+        burn_checked(cpi_ctx, amount, mint.decimals)
+    })
+}
+
+/// Close an empty token account owned by the burn config PDA.
+fn close_config_token_account<'info>(
+    config: &Account<'info, BurnConfig>,
+    token_program: AccountInfo<'info>,
+    account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account,
+        destination,
+        authority: config.to_account_info(),
+    };
+
+    config.with_signer_seeds(|signer| {
+        let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
+        token_interface::close_account(cpi_ctx)
+    })
+}
+
+/// Pay a schedule's crank reward out of the lamports it holds above rent.
+fn pay_crank_reward<'info>(
+    schedule: &Account<'info, BurnSchedule>,
+    cranker: &AccountInfo<'info>,
+) -> Result<u64> {
+    let schedule_info = schedule.to_account_info();
+    let rent = Rent::get()?.minimum_balance(schedule_info.data_len());
+    let reward = schedule
+        .crank_reward
+        .min(schedule_info.lamports().saturating_sub(rent));
+
+    **schedule_info.try_borrow_mut_lamports()? -= reward;
+    **cranker.try_borrow_mut_lamports()? += reward;
+
+    Ok(reward)
+}
+
+/// Transfer `amount` out of a token account owned by the burn config PDA.
+fn transfer_from_config_account<'info>(
+    config: &Account<'info, BurnConfig>,
+    token_program: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from,
+        mint: mint.to_account_info(),
+        to,
+        authority: config.to_account_info(),
+    };
+
+    config.with_signer_seeds(|signer| {
+        let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)
+    })
+}
+
+/// Close a program-owned account that is not held in an `Account` wrapper,
+/// sending its lamports to `destination`.
+fn close_program_account<'info>(account: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    let lamports = account.lamports();
+    **destination.lamports.borrow_mut() = destination.lamports().checked_add(lamports).unwrap();
+    **account.lamports.borrow_mut() = 0;
+
+    account.assign(&System::id());
+    account.realloc(0, false)?;
+
+    Ok(())
+}
+
+/// Ensure `executor` may burn `amount` from `token_account`, either as its
+/// owner or as an SPL delegate with a sufficient approval.
+fn check_burn_source(token_account: &TokenAccount, executor: &Pubkey, amount: u64) -> Result<()> {
+    let is_owner = token_account.owner == *executor;
+    let is_delegate = token_account.delegate.contains(executor)
+        && token_account.delegated_amount >= amount;
+    require!(is_owner || is_delegate, ErrorCode::InvalidBurnSource);
+    Ok(())
+}
+
+/// Read a program-wide singleton such as `GlobalConfig` or `FeeSchedule`
+/// from its (seeds-checked) PDA, or `None` while it has not been initialized.
+fn load_if_initialized<T: AccountDeserialize>(info: &AccountInfo) -> Result<Option<T>> {
+    if info.data_is_empty() {
+        return Ok(None);
+    }
+    if *info.owner != crate::ID {
+        return err!(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram);
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(T::try_deserialize(&mut &data[..])?))
+}
+
+/// Size of the Ed25519 program's per-signature offsets record
+const ED25519_OFFSETS_SIZE: usize = 14;
+/// Offset of the first offsets record (after num_signatures + padding)
+const ED25519_OFFSETS_START: usize = 2;
+
+/// Search the transaction for an Ed25519 program instruction in which `signer`
+/// signed a borsh-encoded `T` accepted by `matches`.
+///
+/// The Ed25519 precompile has already verified every signature by the time we
+/// run; we only need to make sure the public key and message it checked are
+/// the ones we read, i.e. that they live inside the Ed25519 instruction itself.
+fn load_ed25519_attestation<T: AnchorDeserialize>(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    matches: impl Fn(&T) -> bool,
+) -> Result<Option<T>> {
+    let current_index = load_current_index_checked(instructions)? as usize;
+
+    for index in 0..current_index {
+        let ix = load_instruction_at_checked(index, instructions)?;
+        if ix.program_id != ed25519_program::ID || ix.data.len() < ED25519_OFFSETS_START {
+            continue;
+        }
+
+        let data = &ix.data;
+        let num_signatures = data[0] as usize;
+        for i in 0..num_signatures {
+            let start = ED25519_OFFSETS_START + i * ED25519_OFFSETS_SIZE;
+            let Some(offsets) = data.get(start..start + ED25519_OFFSETS_SIZE) else {
+                break;
+            };
+            let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+            // signature, public key and message must all come from this instruction
+            if read_u16(2) != u16::MAX || read_u16(6) != u16::MAX || read_u16(12) != u16::MAX {
+                continue;
+            }
+
+            let pubkey_offset = read_u16(4) as usize;
+            let message_offset = read_u16(8) as usize;
+            let message_size = read_u16(10) as usize;
+
+            let Some(pubkey) = data.get(pubkey_offset..pubkey_offset + 32) else {
+                continue;
+            };
+            if pubkey != signer.as_ref() {
+                continue;
+            }
+
+            let Some(mut message) = data.get(message_offset..message_offset + message_size) else {
+                continue;
+            };
+            if let Ok(payload) = T::deserialize(&mut message) {
+                if message.is_empty() && matches(&payload) {
+                    return Ok(Some(payload));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Load and check the AI decision for a burn, if `config` requires one
+///
+/// The caller advances `ai_decision_nonce` once the burn goes through.
+fn load_ai_decision(
+    config: &Account<BurnConfig>,
+    instructions: &AccountInfo,
+    amount: u64,
+) -> Result<Option<AiDecision>> {
+    let Some(ai_signer) = config.ai_signer else {
+        return Ok(None);
+    };
+
+    let decision: AiDecision = load_ed25519_attestation(
+        instructions,
+        &ai_signer,
+        |decision: &AiDecision| {
+            decision.burn_config == config.key() && decision.nonce == config.ai_decision_nonce
+        },
+    )?
+    .ok_or(ErrorCode::AiDecisionMissing)?;
+    require!(
+        decision.expiry_slot >= Clock::get()?.slot,
+        ErrorCode::AiDecisionExpired
+    );
+    require!(decision.approved, ErrorCode::AiDecisionRejected);
+    require!(
+        amount <= decision.max_amount,
+        ErrorCode::AiDecisionAmountExceeded
+    );
+    require!(
+        decision.confidence >= config.min_ai_confidence,
+        ErrorCode::AiConfidenceTooLow
+    );
+    require!(
+        !config.require_positive_sentiment || decision.sentiment == Sentiment::Positive,
+        ErrorCode::AiSentimentNotPositive
+    );
+
+    Ok(Some(decision))
+}
+
+#[derive(Accounts)]
+#[instruction(config_id: u64)]
+pub struct InitializeBurnConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BurnConfig::INIT_SPACE,
+        seeds = [
+            b"burn_config",
+            authority.key().as_ref(),
+            token_mint.key().as_ref(),
+            &config_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Mint the x402 fee is paid in (e.g. USDC)
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account that receives x402 fees
+    #[account(token::mint = payment_mint)]
+    pub payment_treasury: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(config_id: u64)]
+pub struct MigrateBurnConfig<'info> {
+    /// CHECK: legacy mint-only config, validated by seeds and owner and
+    /// decoded by `LegacyBurnConfig::load`
+    #[account(
+        mut,
+        seeds = [b"burn_config", token_mint.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub legacy_config: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BurnConfig::INIT_SPACE,
+        seeds = [
+            b"burn_config",
+            authority.key().as_ref(),
+            token_mint.key().as_ref(),
+            &config_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Mint the x402 fee is paid in (e.g. USDC)
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account that receives x402 fees
+    #[account(token::mint = payment_mint)]
+    pub payment_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(mode: BurnMode, x402_signature: String)]
+pub struct ExecuteAutonomousBurn<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = token_mint,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+    
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Owned by or delegated to the executor, or the config's burn vault
+    #[account(
+        mut,
+        token::mint = token_mint,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Config authority or a registered burn operator; receives the rent of
+    /// a source closed by `BurnMode::All`
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    /// CHECK: program-wide `GlobalConfig` PDA, read by `check_active`; burns
+    /// are not globally paused until it is initialized
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// Required when `executor` is not the config authority
+    #[account(
+        mut,
+        seeds = [b"burn_operator", burn_config.key().as_ref(), executor.key().as_ref()],
+        bump = burn_operator.bump,
+    )]
+    pub burn_operator: Option<Account<'info, BurnOperator>>,
+
+    /// Wallet that signed the x402 payment transfer; also funds the receipt
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Replay guard: fails to init if this x402 payment was already used.
+    /// Required in `VerifiedTransfer` mode, optional otherwise
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PaymentReceipt::INIT_SPACE,
+        seeds = [b"payment_receipt".as_ref(), &hash(x402_signature.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub payment_receipt: Option<Account<'info, PaymentReceipt>>,
+
+    /// CHECK: Instructions sysvar, used to inspect the x402 payment transfer
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Payer's payment-mint account (`ProgramCollected` mode only)
+    #[account(mut)]
+    pub payment_source: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Config's payment mint (`ProgramCollected` mode only)
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Config's payment treasury (`ProgramCollected` mode only)
+    #[account(mut)]
+    pub payment_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program owning the payment mint (`ProgramCollected` mode only)
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Payer's prepaid credits (`PrepaidCredits` mode only)
+    #[account(
+        mut,
+        seeds = [b"credit_account", burn_config.key().as_ref(), payer.key().as_ref()],
+        bump = credit_account.bump,
+    )]
+    pub credit_account: Option<Account<'info, CreditAccount>>,
+
+    /// CHECK: program-wide `FeeSchedule` PDA, read with `load_if_initialized`;
+    /// burns pay no protocol fee until it is initialized
+    #[account(seeds = [b"fee_schedule"], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// Present when the config authority is exempt from the protocol fee
+    #[account(
+        seeds = [b"fee_exemption", burn_config.authority.as_ref()],
+        bump = fee_exemption.bump,
+    )]
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    /// Fee destination's account for the burned mint (when a fee is due)
+    #[account(mut)]
+    pub fee_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Must match `burn_config.price_feed`; parsed by `PythPrice::load`
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Config's PnL ledger (`ProfitSource::Ledger` only)
+    #[account(
+        mut,
+        seeds = [b"pnl_ledger", burn_config.key().as_ref()],
+        bump = pnl_ledger.load()?.bump,
+    )]
+    pub pnl_ledger: Option<AccountLoader<'info, PnlLedger>>,
+}
+
+impl<'info> ExecuteAutonomousBurn<'info> {
+    /// Profit behind the burn, per the config's profit source and mode
+    fn profit_trigger(&self) -> Result<ProfitTrigger> {
+        let config = &self.burn_config;
+
+        if config.profit_source == ProfitSource::Ledger {
+            let ledger = self
+                .pnl_ledger
+                .as_ref()
+                .ok_or(ErrorCode::PnlLedgerMissing)?
+                .load()?;
+            let (from_sequence, to_sequence) = ledger.unconsumed_range();
+            require!(from_sequence < to_sequence, ErrorCode::NoLedgerTrades);
+            let (cost_basis, proceeds) = ledger.totals(from_sequence, to_sequence);
+            let pnl = i64::try_from(proceeds as i128 - cost_basis as i128)
+                .map_err(|_| error!(ErrorCode::ProfitLedgerOverflow))?;
+            let profit = pnl.max(0) as u128;
+
+            return Ok(ProfitTrigger {
+                profit_amount: profit as u64,
+                pnl,
+                roi_bps: (config.profit_mode == ProfitMode::RoiBps)
+                    .then(|| u64::try_from(profit * 10000 / cost_basis.max(1)).unwrap_or(u64::MAX)),
+                period_id: config.last_profit_period,
+                ledger_range: Some((from_sequence, to_sequence)),
+            });
+        }
+
+        let instructions = self.instructions.to_account_info();
+        let (report, roi_bps) = match config.profit_mode {
+            ProfitMode::Absolute => {
+                let report: ProfitReport = load_ed25519_attestation(
+                    &instructions,
+                    &config.profit_oracle,
+                    |report: &ProfitReport| report.burn_config == config.key(),
+                )?
+                .ok_or(ErrorCode::ProfitAttestationMissing)?;
+                (report, None)
+            }
+            ProfitMode::RoiBps => {
+                let roi: RoiReport = load_ed25519_attestation(
+                    &instructions,
+                    &config.profit_oracle,
+                    |roi: &RoiReport| roi.burn_config == config.key(),
+                )?
+                .ok_or(ErrorCode::ProfitAttestationMissing)?;
+                let roi_bps = roi.roi_bps()?;
+                (roi.into_profit_report(), Some(roi_bps))
+            }
+        };
+        require!(
+            report.expiry_slot >= Clock::get()?.slot,
+            ErrorCode::ProfitAttestationExpired
+        );
+        require!(
+            report.period_id > config.last_profit_period,
+            ErrorCode::ProfitPeriodAlreadyUsed
+        );
+
+        Ok(ProfitTrigger {
+            profit_amount: report.profit_amount,
+            pnl: i64::try_from(report.profit_amount)
+                .map_err(|_| error!(ErrorCode::ProfitLedgerOverflow))?,
+            roi_bps,
+            period_id: report.period_id,
+            ledger_range: None,
+        })
+    }
+
+    /// Transfer the protocol fee from the burn source to the fee destination
+    ///
+    /// Only called for executor-held sources; vault burns are fee-free.
+    fn collect_protocol_fee(&self, schedule: &FeeSchedule, fee: u64) -> Result<()> {
+        transfer_protocol_fee(
+            schedule,
+            self.fee_token_account.as_ref(),
+            &self.token_account,
+            &self.token_mint,
+            &self.executor,
+            &self.token_program,
+            fee,
+        )
+    }
+
+    /// Take the x402 fee per the config's `payment_mode`
+    fn take_x402_payment(&mut self) -> Result<u64> {
+        take_x402_payment(
+            &self.burn_config,
+            &self.payer,
+            &self.instructions,
+            self.payment_receipt.is_some(),
+            X402PaymentAccounts {
+                source: self.payment_source.as_ref(),
+                mint: self.payment_mint.as_ref(),
+                treasury: self.payment_treasury.as_ref(),
+                token_program: self.payment_token_program.as_ref(),
+            },
+            self.credit_account.as_mut(),
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePnlLedger<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Mint every recorded trade is quoted in (e.g. USDC or wrapped SOL)
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<PnlLedger>(),
+        seeds = [b"pnl_ledger", burn_config.key().as_ref()],
+        bump
+    )]
+    pub pnl_ledger: AccountLoader<'info, PnlLedger>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordTrade<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"pnl_ledger", burn_config.key().as_ref()],
+        bump = pnl_ledger.load()?.bump,
+    )]
+    pub pnl_ledger: AccountLoader<'info, PnlLedger>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    /// Config authority or an operator with the record-trades permission
+    pub recorder: Signer<'info>,
+
+    /// Required when `recorder` is not the config authority
+    #[account(
+        seeds = [b"burn_operator", burn_config.key().as_ref(), recorder.key().as_ref()],
+        bump = burn_operator.bump,
+    )]
+    pub burn_operator: Option<Account<'info, BurnOperator>>,
+}
+
+#[derive(Accounts)]
+pub struct SettlePnlLedger<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"pnl_ledger", burn_config.key().as_ref()],
+        bump = pnl_ledger.load()?.bump,
+    )]
+    pub pnl_ledger: AccountLoader<'info, PnlLedger>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordProfitPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the oracle's Ed25519 attestation
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(min_amount_out: u64, swap_data: Vec<u8>, x402_signature: String)]
+pub struct BuybackAndBurn<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = token_mint,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Receives the swap output; executor-owned or the config's burn vault
+    #[account(mut, token::mint = token_mint)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Config authority or a registered burn operator
+    pub executor: Signer<'info>,
+
+    /// Required when `executor` is not the config authority
+    #[account(
+        mut,
+        seeds = [b"burn_operator", burn_config.key().as_ref(), executor.key().as_ref()],
+        bump = burn_operator.bump,
+    )]
+    pub burn_operator: Option<Account<'info, BurnOperator>>,
+
+    /// CHECK: program-wide `GlobalConfig` PDA, read by `check_active`; burns
+    /// are not globally paused until it is initialized
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the config's `swap_programs` allowlist
+    #[account(executable)]
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// Wallet that signed the x402 payment transfer; also funds the receipt
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Replay guard, as in `execute_autonomous_burn`. Required in
+    /// `VerifiedTransfer` mode
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PaymentReceipt::INIT_SPACE,
+        seeds = [b"payment_receipt".as_ref(), &hash(x402_signature.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub payment_receipt: Option<Account<'info, PaymentReceipt>>,
+
+    /// CHECK: Instructions sysvar, used to read the AI signer's decision and
+    /// the x402 payment transfer
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Payer's payment-mint account (`ProgramCollected` mode only)
+    #[account(mut)]
+    pub payment_source: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Config's payment mint (`ProgramCollected` mode only)
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Config's payment treasury (`ProgramCollected` mode only)
+    #[account(mut)]
+    pub payment_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program owning the payment mint (`ProgramCollected` mode only)
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Payer's prepaid credits (`PrepaidCredits` mode only)
+    #[account(
+        mut,
+        seeds = [b"credit_account", burn_config.key().as_ref(), payer.key().as_ref()],
+        bump = credit_account.bump,
+    )]
+    pub credit_account: Option<Account<'info, CreditAccount>>,
+
+    /// CHECK: program-wide `FeeSchedule` PDA, read with `load_if_initialized`;
+    /// buybacks pay no protocol fee until it is initialized
+    #[account(seeds = [b"fee_schedule"], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// Present when the config authority is exempt from the protocol fee
+    #[account(
+        seeds = [b"fee_exemption", burn_config.authority.as_ref()],
+        bump = fee_exemption.bump,
+    )]
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    /// Fee destination's account for the burned mint (when a fee is due)
+    #[account(mut)]
+    pub fee_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+impl<'info> BuybackAndBurn<'info> {
+    /// Take the x402 fee per the config's `payment_mode`
+    fn take_x402_payment(&mut self) -> Result<u64> {
+        take_x402_payment(
+            &self.burn_config,
+            &self.payer,
+            &self.instructions,
+            self.payment_receipt.is_some(),
+            X402PaymentAccounts {
+                source: self.payment_source.as_ref(),
+                mint: self.payment_mint.as_ref(),
+                treasury: self.payment_treasury.as_ref(),
+                token_program: self.payment_token_program.as_ref(),
+            },
+            self.credit_account.as_mut(),
+        )
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(x402_signature: String)]
+pub struct BurnAllAndClose<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = token_mint,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Executor-owned account or the config's burn vault
+    #[account(mut, token::mint = token_mint)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Config authority or a registered burn operator
+    pub executor: Signer<'info>,
+
+    /// Required when `executor` is not the config authority
+    #[account(
+        mut,
+        seeds = [b"burn_operator", burn_config.key().as_ref(), executor.key().as_ref()],
+        bump = burn_operator.bump,
+    )]
+    pub burn_operator: Option<Account<'info, BurnOperator>>,
+
+    /// CHECK: program-wide `GlobalConfig` PDA, read by `check_active`; burns
+    /// are not globally paused until it is initialized
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// CHECK: Any account; receives the closed account's rent
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Wallet that signed the x402 payment transfer; also funds the receipt
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Replay guard, as in `execute_autonomous_burn`. Required in
+    /// `VerifiedTransfer` mode when there is a balance to burn
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PaymentReceipt::INIT_SPACE,
+        seeds = [b"payment_receipt".as_ref(), &hash(x402_signature.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub payment_receipt: Option<Account<'info, PaymentReceipt>>,
+
+    /// CHECK: Instructions sysvar, used to read the AI signer's decision and
+    /// the x402 payment transfer
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Payer's payment-mint account (`ProgramCollected` mode only)
+    #[account(mut)]
+    pub payment_source: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Config's payment mint (`ProgramCollected` mode only)
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Config's payment treasury (`ProgramCollected` mode only)
+    #[account(mut)]
+    pub payment_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program owning the payment mint (`ProgramCollected` mode only)
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Payer's prepaid credits (`PrepaidCredits` mode only)
+    #[account(
+        mut,
+        seeds = [b"credit_account", burn_config.key().as_ref(), payer.key().as_ref()],
+        bump = credit_account.bump,
+    )]
+    pub credit_account: Option<Account<'info, CreditAccount>>,
+
+    /// CHECK: program-wide `FeeSchedule` PDA, read with `load_if_initialized`;
+    /// retirements pay no protocol fee until it is initialized
+    #[account(seeds = [b"fee_schedule"], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// Present when the config authority is exempt from the protocol fee
+    #[account(
+        seeds = [b"fee_exemption", burn_config.authority.as_ref()],
+        bump = fee_exemption.bump,
+    )]
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    /// Fee destination's account for the burned mint (when a fee is due)
+    #[account(mut)]
+    pub fee_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+impl<'info> BurnAllAndClose<'info> {
+    /// Take the x402 fee per the config's `payment_mode`
+    fn take_x402_payment(&mut self) -> Result<u64> {
+        take_x402_payment(
+            &self.burn_config,
+            &self.payer,
+            &self.instructions,
+            self.payment_receipt.is_some(),
+            X402PaymentAccounts {
+                source: self.payment_source.as_ref(),
+                mint: self.payment_mint.as_ref(),
+                treasury: self.payment_treasury.as_ref(),
+                token_program: self.payment_token_program.as_ref(),
+            },
+            self.credit_account.as_mut(),
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateBurnConfig<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + GlobalConfig::INIT_SPACE,
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// This program's ProgramData, at its address under the upgradeable loader
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ErrorCode::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGlobalPaused<'info> {
+    #[account(mut, seeds = [b"global_config"], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// This program's ProgramData, at its address under the upgradeable loader
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ErrorCode::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeSchedule<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeeSchedule::INIT_SPACE,
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    /// This program's ProgramData, at its address under the upgradeable loader
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ErrorCode::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeSchedule<'info> {
+    #[account(mut, seeds = [b"fee_schedule"], bump = fee_schedule.bump)]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    /// This program's ProgramData, at its address under the upgradeable loader
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ErrorCode::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddFeeExemption<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeeExemption::INIT_SPACE,
+        seeds = [b"fee_exemption", wallet.as_ref()],
+        bump
+    )]
+    pub fee_exemption: Account<'info, FeeExemption>,
+
+    /// This program's ProgramData, at its address under the upgradeable loader
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ErrorCode::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveFeeExemption<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"fee_exemption", fee_exemption.wallet.as_ref()],
+        bump = fee_exemption.bump,
+    )]
+    pub fee_exemption: Account<'info, FeeExemption>,
+
+    /// This program's ProgramData, at its address under the upgradeable loader
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ErrorCode::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBurnVault<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+        has_one = token_mint,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"burn_vault", burn_config.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = burn_config,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateBurnSchedule<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BurnSchedule::INIT_SPACE,
+        seeds = [b"burn_schedule", burn_config.key().as_ref()],
+        bump
+    )]
+    pub burn_schedule: Account<'info, BurnSchedule>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundBurnSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [b"burn_schedule", burn_schedule.burn_config.as_ref()],
+        bump = burn_schedule.bump,
+    )]
+    pub burn_schedule: Account<'info, BurnSchedule>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankScheduledBurn<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = token_mint,
+        constraint = burn_config.vault == Some(vault.key()) @ ErrorCode::BurnVaultMissing,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"burn_schedule", burn_config.key().as_ref()],
+        bump = burn_schedule.bump,
+    )]
+    pub burn_schedule: Account<'info, BurnSchedule>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: program-wide `GlobalConfig` PDA, read by `check_active`; burns
+    /// are not globally paused until it is initialized
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// Anyone; receives the crank reward
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBurnSchedule<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"burn_schedule", burn_config.key().as_ref()],
+        bump = burn_schedule.bump,
+    )]
+    pub burn_schedule: Account<'info, BurnSchedule>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CreateBurnOrder<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+        has_one = token_mint,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BurnOrder::INIT_SPACE,
+        seeds = [b"burn_order", burn_config.key().as_ref(), &order_id.to_le_bytes()],
+        bump
+    )]
+    pub burn_order: Account<'info, BurnOrder>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"burn_order_escrow", burn_order.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = burn_config,
+        token::token_program = token_program,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// Authority's tokens funding the order
+    #[account(mut, token::mint = token_mint)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankBurnOrder<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = token_mint,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"burn_order", burn_config.key().as_ref(), &burn_order.order_id.to_le_bytes()],
+        bump = burn_order.bump,
+        has_one = escrow,
+    )]
+    pub burn_order: Account<'info, BurnOrder>,
+
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: program-wide `GlobalConfig` PDA, read by `check_active`; burns
+    /// are not globally paused until it is initialized
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelBurnOrder<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+        has_one = token_mint,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"burn_order", burn_config.key().as_ref(), &burn_order.order_id.to_le_bytes()],
+        bump = burn_order.bump,
+        has_one = escrow,
+    )]
+    pub burn_order: Account<'info, BurnOrder>,
+
+    #[account(mut)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the unburned remainder
+    #[account(mut, token::mint = token_mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCreditVault<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+        has_one = payment_mint,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"credit_vault", burn_config.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = burn_config,
+        token::token_program = token_program,
+    )]
+    pub credit_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenCreditAccount<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + CreditAccount::INIT_SPACE,
+        seeds = [b"credit_account", burn_config.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub credit_account: Account<'info, CreditAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositCredits<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = payment_mint,
+        constraint = burn_config.credit_vault == Some(credit_vault.key())
+            @ ErrorCode::InvalidPaymentAccount,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, has_one = burn_config)]
+    pub credit_account: Account<'info, CreditAccount>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub credit_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = payment_mint)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    pub depositor: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCredits<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = payment_mint,
+        constraint = burn_config.credit_vault == Some(credit_vault.key())
+            @ ErrorCode::InvalidPaymentAccount,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, has_one = burn_config, has_one = owner)]
+    pub credit_account: Account<'info, CreditAccount>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub credit_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = payment_mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SweepCreditFees<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = payment_mint,
+        has_one = payment_treasury,
+        constraint = burn_config.credit_vault == Some(credit_vault.key())
+            @ ErrorCode::InvalidPaymentAccount,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub credit_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payment_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct OperatorUpdateBurnConfig<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"burn_operator", burn_config.key().as_ref(), operator.key().as_ref()],
+        bump = burn_operator.bump,
+    )]
+    pub burn_operator: Account<'info, BurnOperator>,
+
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(operator: Pubkey)]
+pub struct AddBurnOperator<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BurnOperator::INIT_SPACE,
+        seeds = [b"burn_operator", burn_config.key().as_ref(), operator.as_ref()],
+        bump
+    )]
+    pub burn_operator: Account<'info, BurnOperator>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveBurnOperator<'info> {
+    #[account(
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        has_one = authority,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = burn_config,
+    )]
+    pub burn_operator: Account<'info, BurnOperator>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"burn_config",
+            burn_config.creator.as_ref(),
+            token_mint.key().as_ref(),
+            &burn_config.config_id.to_le_bytes(),
+        ],
+        bump = burn_config.bump,
+        constraint = burn_config.pending_authority == Some(pending_authority.key())
+            @ ErrorCode::NotPendingAuthority,
+    )]
+    pub burn_config: Account<'info, BurnConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub pending_authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BurnConfig {
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub profit_threshold: u64,
+    pub burn_percentage: u16,
+    pub min_burn_amount: u64,
+    pub total_burned: u64,
+    pub burn_count: u64,
+    pub bump: u8,
+    pub payment_mint: Pubkey,
+    pub payment_treasury: Pubkey,
+    pub payment_amount: u64,
+    pub profit_oracle: Pubkey,
+    pub last_profit_period: u64,
+    pub pending_authority: Option<Pubkey>,
+    pub vault: Option<Pubkey>,
+    /// Largest single burn (0 = no cap)
+    pub max_burn_amount: u64,
+    /// Length of the burn window in seconds
+    pub window_seconds: i64,
+    /// Most tokens burned per window (0 = no cap)
+    pub window_max_burned: u64,
+    /// Minimum seconds between two burns
+    pub min_burn_interval: i64,
+    pub window_start: i64,
+    pub window_burned: u64,
+    pub last_burn_at: i64,
+    /// Largest single burn as basis points of mint supply (0 = no cap)
+    pub max_supply_bps: u16,
+    pub paused: bool,
+    /// Auto-pause after a single burn takes more than this share of the
+    /// source (vault) balance, in basis points (0 = off)
+    pub auto_pause_bps: u16,
+    /// Authority that created the config; part of the PDA seeds
+    pub creator: Pubkey,
+    /// Caller-chosen id; part of the PDA seeds
+    pub config_id: u64,
+    pub payment_mode: PaymentMode,
+    pub credit_vault: Option<Pubkey>,
+    /// Credits debited by burns and not yet swept to the payment treasury
+    pub credit_fees_accrued: u64,
+    /// Pyth-compatible feed pricing the token in the profit's quote asset
+    pub price_feed: Option<Pubkey>,
+    pub quote_decimals: u8,
+    pub max_price_age: i64,
+    pub max_price_confidence_bps: u16,
+    /// How `profit_threshold` is compared against attested profit
+    pub profit_mode: ProfitMode,
+    /// Net realized profit over all recorded periods, in profit units
+    pub cumulative_profit: i64,
+    /// Cumulative profit already covered by burns
+    pub high_water_mark: i64,
+    /// Share of a drawdown below the mark that lowers the mark (0 = hold, 10000 = reset)
+    pub drawdown_reset_bps: u16,
+    /// Where burns read the triggering profit from
+    pub profit_source: ProfitSource,
+    /// Key whose signed `AiDecision` every burn needs, if set
+    pub ai_signer: Option<Pubkey>,
+    /// Lowest accepted AI confidence (0-100)
+    pub min_ai_confidence: u8,
+    pub require_positive_sentiment: bool,
+    /// Keys that co-sign large burns
+    #[max_len(10)]
+    pub guardians: Vec<Pubkey>,
+    /// Guardian signatures required for burns of `quorum_burn_threshold` or more
+    pub guardian_quorum: u8,
+    pub quorum_burn_threshold: u64,
+    /// Programs `buyback_and_burn` may swap through
+    #[max_len(4)]
+    pub swap_programs: Vec<Pubkey>,
+    /// Nonce the next `AiDecision` must carry; advanced by each approved burn
+    pub ai_decision_nonce: u64,
+}
+
+/// How `execute_autonomous_burn` obtains the x402 fee
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaymentMode {
+    /// A separate `transfer_checked` right before the burn, checked via the
+    /// Instructions sysvar
+    VerifiedTransfer,
+    /// The program transfers the fee itself, atomically with the burn
+    ProgramCollected,
+    /// The fee is debited from the payer's prepaid `CreditAccount`
+    PrepaidCredits,
+}
+
+/// What `profit_threshold` measures
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProfitMode {
+    /// Absolute profit from a `ProfitReport`
+    Absolute,
+    /// Return on cost basis in basis points, from a `RoiReport`
+    RoiBps,
+}
+
+/// Where `execute_autonomous_burn` reads the triggering profit from
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProfitSource {
+    /// An oracle-signed `ProfitReport` / `RoiReport` in the transaction
+    Attested,
+    /// Trades in the config's `PnlLedger` not yet consumed by a burn
+    Ledger,
+}
+
+impl BurnConfig {
+    pub const MAX_GUARDIANS: usize = 10;
+    pub const MAX_SWAP_PROGRAMS: usize = 4;
+
+    fn requires_quorum(&self, amount: u64) -> bool {
+        self.quorum_burn_threshold > 0 && amount >= self.quorum_burn_threshold
+    }
+
+    /// Count distinct guardians among the signers in `accounts`
+    fn check_quorum(&self, accounts: &[AccountInfo]) -> Result<()> {
+        let mut signed = Vec::with_capacity(self.guardians.len());
+        for account in accounts.iter().filter(|account| account.is_signer) {
+            if self.guardians.contains(account.key) && !signed.contains(account.key) {
+                signed.push(*account.key);
+            }
+        }
+        require!(
+            signed.len() >= self.guardian_quorum as usize,
+            ErrorCode::QuorumNotMet
+        );
+
+        Ok(())
+    }
+
+    /// Run `f` with the signer seeds of this config's PDA
+    fn with_signer_seeds<R>(&self, f: impl FnOnce(&[&[&[u8]]]) -> R) -> R {
+        let config_id = self.config_id.to_le_bytes();
+        let bump = [self.bump];
+        let seeds: &[&[u8]] = &[
+            b"burn_config",
+            self.creator.as_ref(),
+            self.token_mint.as_ref(),
+            &config_id,
+            &bump,
+        ];
+        f(&[seeds])
+    }
+
+    /// Cumulative profit after adding `pnl`, and how much of it is above the
+    /// high-water mark
+    fn profit_above_high_water_mark(&self, pnl: i64) -> Result<(i64, u64)> {
+        let cumulative = self
+            .cumulative_profit
+            .checked_add(pnl)
+            .ok_or(ErrorCode::ProfitLedgerOverflow)?;
+        let eligible = cumulative.saturating_sub(self.high_water_mark).max(0) as u64;
+
+        Ok((cumulative, eligible))
+    }
+
+    /// Add a period's profit or loss, applying the drawdown policy to the mark
+    fn record_pnl(&mut self, pnl: i64) -> Result<()> {
+        self.cumulative_profit = self
+            .cumulative_profit
+            .checked_add(pnl)
+            .ok_or(ErrorCode::ProfitLedgerOverflow)?;
+
+        if self.cumulative_profit < self.high_water_mark {
+            let drawdown = (self.high_water_mark as i128) - (self.cumulative_profit as i128);
+            let reset = drawdown * self.drawdown_reset_bps as i128 / 10000;
+            self.high_water_mark -= reset as i64;
+        }
+
+        Ok(())
+    }
+
+    /// Derive the burn config PDA for `creator`, `token_mint` and `config_id`
+    pub fn pda(creator: &Pubkey, token_mint: &Pubkey, config_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"burn_config",
+                creator.as_ref(),
+                token_mint.as_ref(),
+                &config_id.to_le_bytes(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    fn check_active(&self, global_config: &AccountInfo) -> Result<()> {
+        if let Some(global) = load_if_initialized::<GlobalConfig>(global_config)? {
+            require!(!global.paused, ErrorCode::ProgramPaused);
+        }
+        require!(!self.paused, ErrorCode::ConfigPaused);
+        Ok(())
+    }
+
+    /// Whether burning `amount` out of `source_balance` trips the auto-pause
+    fn should_auto_pause(&self, amount: u64, source_balance: u64) -> bool {
+        self.auto_pause_bps > 0
+            && (amount as u128) * 10000 > (source_balance as u128) * self.auto_pause_bps as u128
+    }
+
+    /// Check `amount` against the per-burn, supply-share, per-window and
+    /// cooldown limits, then record it in the window and lifetime totals.
+    ///
+    /// `supply` is the mint supply read before the burn.
+    fn record_burn(&mut self, amount: u64, supply: u64, now: i64) -> Result<()> {
+        require!(
+            self.max_burn_amount == 0 || amount <= self.max_burn_amount,
+            ErrorCode::BurnExceedsMaxAmount
+        );
+
+        if self.max_supply_bps > 0 {
+            let max_from_supply = (supply as u128)
+                .checked_mul(self.max_supply_bps as u128)
+                .unwrap()
+                / 10000;
+            require!(
+                amount as u128 <= max_from_supply,
+                ErrorCode::BurnExceedsSupplyShare
+            );
+        }
+
+        if self.burn_count > 0 {
+            require!(
+                now >= self.last_burn_at.saturating_add(self.min_burn_interval),
+                ErrorCode::BurnCooldownActive
+            );
+        }
+
+        if now >= self.window_start.saturating_add(self.window_seconds) {
+            self.window_start = now;
+            self.window_burned = 0;
+        }
+        let window_burned = self.window_burned.checked_add(amount).unwrap();
+        require!(
+            self.window_max_burned == 0 || window_burned <= self.window_max_burned,
+            ErrorCode::WindowBurnCapExceeded
+        );
+
+        self.window_burned = window_burned;
+        self.last_burn_at = now;
+        self.total_burned = self.total_burned.checked_add(amount).unwrap();
+        self.burn_count = self.burn_count.checked_add(1).unwrap();
+
+        Ok(())
+    }
+}
+
+/// Aggregate price read from a Pyth legacy (v2) price account
+///
+/// Only the fields the program needs are decoded: the exponent, the publish
+/// timestamp and the aggregate price, confidence and status.
+pub struct PythPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    pub const MAGIC: u32 = 0xa1b2c3d4;
+    pub const ACCOUNT_TYPE_PRICE: u32 = 3;
+    pub const STATUS_TRADING: u32 = 1;
+
+    pub const MAGIC_OFFSET: usize = 0;
+    pub const ACCOUNT_TYPE_OFFSET: usize = 8;
+    pub const EXPO_OFFSET: usize = 20;
+    pub const TIMESTAMP_OFFSET: usize = 96;
+    pub const AGG_PRICE_OFFSET: usize = 208;
+    pub const AGG_CONF_OFFSET: usize = 216;
+    pub const AGG_STATUS_OFFSET: usize = 224;
+    /// Bytes up to and including the aggregate price info
+    pub const MIN_LEN: usize = 240;
+
+    fn load(account: &AccountInfo) -> Result<Self> {
+        let data = account.try_borrow_data()?;
+        require!(data.len() >= Self::MIN_LEN, ErrorCode::InvalidPriceFeed);
+
+        let read_u32 = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap());
+        let read_u64 = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+
+        require!(read_u32(Self::MAGIC_OFFSET) == Self::MAGIC, ErrorCode::InvalidPriceFeed);
+        require!(
+            read_u32(Self::ACCOUNT_TYPE_OFFSET) == Self::ACCOUNT_TYPE_PRICE,
+            ErrorCode::InvalidPriceFeed
+        );
+        require!(
+            read_u32(Self::AGG_STATUS_OFFSET) == Self::STATUS_TRADING,
+            ErrorCode::InvalidPriceFeed
+        );
+
+        Ok(Self {
+            price: read_u64(Self::AGG_PRICE_OFFSET) as i64,
+            conf: read_u64(Self::AGG_CONF_OFFSET),
+            expo: read_u32(Self::EXPO_OFFSET) as i32,
+            publish_time: read_u64(Self::TIMESTAMP_OFFSET) as i64,
+        })
+    }
+
+    /// Reject non-positive, stale or low-confidence prices
+    fn check(&self, now: i64, max_age: i64, max_confidence_bps: u16) -> Result<()> {
+        require!(self.price > 0, ErrorCode::InvalidPriceFeed);
+        require!(
+            now.saturating_sub(self.publish_time) <= max_age,
+            ErrorCode::StalePrice
+        );
+        require!(
+            (self.conf as u128) * 10000 <= (self.price as u128) * (max_confidence_bps as u128),
+            ErrorCode::PriceConfidenceTooWide
+        );
+
+        Ok(())
+    }
+
+    /// Convert `quote_amount` quote base units into token base units
+    ///
+    /// `tokens = quote_amount * 10^token_decimals / (price * 10^expo * 10^quote_decimals)`
+    fn quote_to_tokens(&self, quote_amount: u64, quote_decimals: u8, token_decimals: u8) -> Result<u64> {
+        let scale_up = token_decimals as u32 + self.expo.min(0).unsigned_abs();
+        let scale_down = quote_decimals as u32 + self.expo.max(0) as u32;
+
+        let numerator = 10u128
+            .checked_pow(scale_up)
+            .and_then(|scale| scale.checked_mul(quote_amount as u128));
+        let denominator = 10u128
+            .checked_pow(scale_down)
+            .and_then(|scale| scale.checked_mul(self.price as u128));
+        let (Some(numerator), Some(denominator)) = (numerator, denominator) else {
+            return err!(ErrorCode::PriceConversionOverflow);
+        };
+
+        u64::try_from(numerator / denominator).map_err(|_| error!(ErrorCode::PriceConversionOverflow))
+    }
+}
+
+/// How `execute_autonomous_burn` sizes the burn
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BurnMode {
+    /// Burn exactly `amount`
+    Amount { amount: u64 },
+    /// Burn `bps` basis points of the source balance at execution time
+    BalanceBps { bps: u16 },
+    /// Burn the whole source balance and close the account
+    All,
+}
+
+impl BurnMode {
+    fn resolve(&self, balance: u64) -> Result<u64> {
+        Ok(match *self {
+            BurnMode::Amount { amount } => amount,
+            BurnMode::BalanceBps { bps } => {
+                require!(bps <= 10000, ErrorCode::InvalidBurnPercentage);
+                ((balance as u128) * (bps as u128) / 10000) as u64
+            }
+            BurnMode::All => balance,
+        })
+    }
+}
+
+/// Profit figures signed by the config's profit oracle
+///
+/// The oracle signs the borsh encoding of this struct with an Ed25519 program
+/// instruction placed in the same transaction as the burn.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProfitReport {
+    pub burn_config: Pubkey,
+    pub profit_amount: u64,
+    pub period_id: u64,
+    pub expiry_slot: u64,
+}
+
+/// Burn decision signed by the config's `ai_signer`
+///
+/// Signed like `ProfitReport`. `nonce` must equal the config's
+/// `ai_decision_nonce`, which only burns that consumed a decision advance, so
+/// a decision approves exactly one burn, of at most `max_amount` tokens.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AiDecision {
+    pub burn_config: Pubkey,
+    pub nonce: u64,
+    /// Largest amount, in token base units and before any protocol fee, the
+    /// approved burn may take from its source
+    pub max_amount: u64,
+    pub approved: bool,
+    /// Model confidence, 0-100
+    pub confidence: u8,
+    pub sentiment: Sentiment,
+    /// Hash of the model's reasoning text, kept off-chain
+    pub reasoning_hash: [u8; 32],
+    pub expiry_slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sentiment {
+    Negative,
+    Neutral,
+    Positive,
+}
+
+/// Realized trade figures signed by the profit oracle for `ProfitMode::RoiBps`
+///
+/// Signed the same way as `ProfitReport`; profit is `proceeds - cost_basis`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RoiReport {
+    pub burn_config: Pubkey,
+    pub cost_basis: u64,
+    pub proceeds: u64,
+    pub period_id: u64,
+    pub expiry_slot: u64,
+}
+
+impl RoiReport {
+    /// Realized return on cost basis in basis points (0 on a loss)
+    fn roi_bps(&self) -> Result<u64> {
+        require!(self.cost_basis > 0, ErrorCode::InvalidRoiReport);
+
+        let profit = self.proceeds.saturating_sub(self.cost_basis) as u128;
+        Ok(u64::try_from(profit * 10000 / self.cost_basis as u128).unwrap_or(u64::MAX))
+    }
+
+    /// Realized profit or loss of the period
+    fn pnl(&self) -> Result<i64> {
+        i64::try_from(self.proceeds as i128 - self.cost_basis as i128)
+            .map_err(|_| error!(ErrorCode::ProfitLedgerOverflow))
+    }
+
+    fn into_profit_report(self) -> ProfitReport {
+        ProfitReport {
+            burn_config: self.burn_config,
+            profit_amount: self.proceeds.saturating_sub(self.cost_basis),
+            period_id: self.period_id,
+            expiry_slot: self.expiry_slot,
+        }
+    }
+}
+
+/// Layout of mint-only burn configs at `["burn_config", token_mint]`, as
+/// written by the original program. Frozen: do not add fields here when
+/// `BurnConfig` grows.
+#[derive(AnchorDeserialize)]
+pub struct LegacyBurnConfig {
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub profit_threshold: u64,
+    pub burn_percentage: u16,
+    pub min_burn_amount: u64,
+    pub total_burned: u64,
+    pub burn_count: u64,
+    pub bump: u8,
+}
+
+impl LegacyBurnConfig {
+    fn load(info: &AccountInfo) -> Result<Self> {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == BurnConfig::DISCRIMINATOR,
+            ErrorCode::InvalidLegacyConfig
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::InvalidLegacyConfig))
+    }
+}
+
+/// Program-wide settings controlled by the upgrade authority
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalConfig {
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+/// Program-wide protocol fee applied to every burn
+#[account]
+#[derive(InitSpace)]
+pub struct FeeSchedule {
+    /// Share of each burn sent to `fee_destination` (basis points)
+    pub fee_bps: u16,
+    /// Burns per config before the fee applies
+    pub free_burns: u64,
+    pub fee_destination: Pubkey,
+    pub bump: u8,
+}
+
+impl FeeSchedule {
+    fn set(&mut self, fee_bps: u16, free_burns: u64, fee_destination: Pubkey) -> Result<()> {
+        require!(fee_bps <= 10000, ErrorCode::InvalidFeeBps);
+
+        self.fee_bps = fee_bps;
+        self.free_burns = free_burns;
+        self.fee_destination = fee_destination;
+
+        emit!(FeeScheduleUpdated {
+            fee_bps,
+            free_burns,
+            fee_destination,
+        });
+
+        Ok(())
+    }
+
+    /// Fee owed on a burn of `amount`, given the config's prior burn count
+    fn fee_for(&self, amount: u64, burn_count: u64, exempt: bool) -> u64 {
+        if exempt || burn_count < self.free_burns {
+            return 0;
+        }
+        ((amount as u128) * (self.fee_bps as u128) / 10000) as u64
+    }
+}
+
+/// Marks a config authority as exempt from the protocol fee
+#[account]
+#[derive(InitSpace)]
+pub struct FeeExemption {
+    pub wallet: Pubkey,
+    pub bump: u8,
+}
+
+/// Agent key allowed to act on a burn config within limits
+#[account]
+#[derive(InitSpace)]
+pub struct BurnOperator {
+    pub burn_config: Pubkey,
+    pub operator: Pubkey,
+    /// Remaining tokens this operator may burn
+    pub allowance: u64,
+    pub expires_at: i64,
+    pub permissions: u8,
+    pub bump: u8,
+}
+
+impl BurnOperator {
+    /// May call `execute_autonomous_burn`
+    pub const PERMISSION_BURN: u8 = 1 << 0;
+    /// May call `operator_update_burn_config`
+    pub const PERMISSION_UPDATE_LIMITED: u8 = 1 << 1;
+    /// May call `record_trade`
+    pub const PERMISSION_RECORD_TRADES: u8 = 1 << 2;
+    pub const PERMISSION_ALL: u8 = Self::PERMISSION_BURN
+        | Self::PERMISSION_UPDATE_LIMITED
+        | Self::PERMISSION_RECORD_TRADES;
+
+    fn authorize(&self, permission: u8, now: i64) -> Result<()> {
+        require!(now < self.expires_at, ErrorCode::OperatorExpired);
+        require!(
+            self.permissions & permission == permission,
+            ErrorCode::OperatorPermissionDenied
+        );
+        Ok(())
+    }
+}
+
+/// Realized trades of one burn config, kept as a zero-copy ring buffer
+///
+/// Trade `n` lives in `trades[n % CAPACITY]`. Trades with a sequence below
+/// `consumed_sequence` have already been burned against or settled.
+#[account(zero_copy)]
+pub struct PnlLedger {
+    pub burn_config: Pubkey,
+    pub quote_mint: Pubkey,
+    /// Sequence number the next trade will get
+    pub next_sequence: u64,
+    pub consumed_sequence: u64,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+    pub trades: [TradeRecord; 128],
+}
+
+#[zero_copy]
+pub struct TradeRecord {
+    pub sequence: u64,
+    /// Quote spent opening the position
+    pub entry_amount: u64,
+    /// Quote received closing it
+    pub exit_amount: u64,
+    pub timestamp: i64,
+    pub quote_mint: Pubkey,
+}
+
+impl PnlLedger {
+    pub const CAPACITY: u64 = 128;
+
+    /// Append a trade, refusing to overwrite one that is still unconsumed
+    fn push(&mut self, trade: TradeRecord) -> Result<()> {
+        require!(
+            self.next_sequence - self.consumed_sequence < Self::CAPACITY,
+            ErrorCode::PnlLedgerFull
+        );
+        self.trades[(trade.sequence % Self::CAPACITY) as usize] = trade;
+        self.next_sequence += 1;
+        Ok(())
+    }
+
+    /// Sequences of unconsumed trades, as `from..to`
+    fn unconsumed_range(&self) -> (u64, u64) {
+        (self.consumed_sequence, self.next_sequence)
+    }
+
+    /// Total cost basis and proceeds of trades `from..to`
+    fn totals(&self, from: u64, to: u64) -> (u128, u128) {
+        (from..to)
+            .map(|sequence| &self.trades[(sequence % Self::CAPACITY) as usize])
+            .fold((0, 0), |(cost, proceeds), trade| {
+                (cost + trade.entry_amount as u128, proceeds + trade.exit_amount as u128)
+            })
+    }
+}
+
+/// Profit that justifies one burn
+struct ProfitTrigger {
+    profit_amount: u64,
+    /// Net profit or loss added to cumulative profit
+    pnl: i64,
+    /// Realized ROI in basis points, in `ProfitMode::RoiBps`
+    roi_bps: Option<u64>,
+    /// Attested report period (unchanged for ledger-sourced profit)
+    period_id: u64,
+    /// Ledger trade sequences `from..to` consumed by the burn
+    ledger_range: Option<(u64, u64)>,
+}
+
+/// Recurring vault burn executed by permissionless cranks
+///
+/// Lamports held above rent are the crank bounty.
+#[account]
+#[derive(InitSpace)]
+pub struct BurnSchedule {
+    pub burn_config: Pubkey,
+    pub tick_amount: TickAmount,
+    pub interval_seconds: i64,
+    pub next_tick_at: i64,
+    pub end_time: Option<i64>,
+    /// Lamports paid to the caller of each tick
+    pub crank_reward: u64,
+    pub ticks: u64,
+    pub bump: u8,
+}
+
+/// Large burn split into tranches executed over time
+#[account]
+#[derive(InitSpace)]
+pub struct BurnOrder {
+    pub burn_config: Pubkey,
+    pub order_id: u64,
+    /// Token account at `["burn_order_escrow", burn_order]` holding the unburned tokens
+    pub escrow: Pubkey,
+    pub total_amount: u64,
+    pub burned: u64,
+    pub tranche_count: u16,
+    pub tranches_executed: u16,
+    pub start_time: i64,
+    pub tranche_interval: i64,
+    pub bump: u8,
+}
+
+impl BurnOrder {
+    /// When tranche `index` becomes executable, or `None` past the last one
+    fn tranche_due_at(&self, index: u16) -> Option<i64> {
+        (index < self.tranche_count)
+            .then(|| self.start_time + index as i64 * self.tranche_interval)
+    }
+
+    /// Size of the next tranche; the last one takes the remainder
+    fn next_tranche_amount(&self) -> u64 {
+        if self.tranches_executed + 1 == self.tranche_count {
+            self.total_amount - self.burned
+        } else {
+            self.total_amount / self.tranche_count as u64
+        }
+    }
+}
+
+/// How much a scheduled tick burns
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TickAmount {
+    /// A fixed token amount
+    Fixed { amount: u64 },
+    /// A share of the vault balance at tick time, in basis points
+    BalanceBps { bps: u16 },
+}
+
+impl TickAmount {
+    fn resolve(&self, balance: u64) -> u64 {
+        match *self {
+            TickAmount::Fixed { amount } => amount,
+            TickAmount::BalanceBps { bps } => ((balance as u128) * (bps as u128) / 10000) as u64,
+        }
+    }
+}
+
+impl BurnSchedule {
+    /// Move `next_tick_at` to the first tick after `now`, skipping missed ticks
+    fn advance(&mut self, now: i64) {
+        let missed = (now - self.next_tick_at) / self.interval_seconds;
+        self.next_tick_at += (missed + 1) * self.interval_seconds;
+        self.ticks += 1;
+    }
+}
+
+/// Prepaid x402 credits of one payer on one burn config
+#[account]
+#[derive(InitSpace)]
+pub struct CreditAccount {
+    pub burn_config: Pubkey,
+    pub owner: Pubkey,
+    /// Unspent credits, in payment mint base units
+    pub balance: u64,
+    pub low_balance_threshold: u64,
+    pub bump: u8,
+}
+
+impl CreditAccount {
+    /// Debit a metered burn fee, emitting `LowCreditBalance` when the
+    /// remaining balance drops below the owner's threshold.
+    fn debit(&mut self, fee: u64) -> Result<()> {
+        self.balance = self
+            .balance
+            .checked_sub(fee)
+            .ok_or(ErrorCode::InsufficientCredits)?;
+
+        if self.balance < self.low_balance_threshold {
+            msg!("⚠️ Low credit balance: {}", self.balance);
+            emit!(LowCreditBalance {
+                burn_config: self.burn_config,
+                owner: self.owner,
+                balance: self.balance,
+                threshold: self.low_balance_threshold,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Record of an x402 payment consumed by a burn
+///
+/// Receipts are never closed: closing one would let its PDA be created again
+/// and the same payment proof fund another burn.
+#[account]
+#[derive(InitSpace)]
+pub struct PaymentReceipt {
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub burn_config: Pubkey,
+    pub slot: u64,
+    pub bump: u8,
+}
+
+#[event]
+pub struct BurnEvent {
+    pub authority: Pubkey,
+    pub executor: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    /// Tokens sent to the protocol fee destination instead of burned
+    pub protocol_fee: u64,
+    pub profit_amount: u64,
+    pub profit_period: u64,
+    pub total_burned: u64,
+    pub burn_count: u64,
+    pub supply_before: u64,
+    pub supply_after: u64,
+    pub cumulative_profit: i64,
+    pub high_water_mark: i64,
+    /// Reasoning hash of the AI decision that approved the burn
+    pub ai_reasoning_hash: Option<[u8; 32]>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradeRecorded {
+    pub burn_config: Pubkey,
+    pub sequence: u64,
+    pub entry_amount: u64,
+    pub exit_amount: u64,
+    pub quote_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LedgerSettled {
+    pub burn_config: Pubkey,
+    pub from_sequence: u64,
+    pub to_sequence: u64,
+    pub pnl: i64,
+    pub cumulative_profit: i64,
+    pub high_water_mark: i64,
+}
+
+#[event]
+pub struct LedgerProfitConsumed {
+    pub burn_config: Pubkey,
+    pub from_sequence: u64,
+    pub to_sequence: u64,
+    pub profit_amount: u64,
+}
+
+#[event]
+pub struct ProfitPeriodRecorded {
+    pub burn_config: Pubkey,
+    pub period_id: u64,
+    pub pnl: i64,
+    pub cumulative_profit: i64,
+    pub high_water_mark: i64,
+}
+
+#[event]
+pub struct FeeScheduleUpdated {
+    pub fee_bps: u16,
+    pub free_burns: u64,
+    pub fee_destination: Pubkey,
+}
+
+#[event]
+pub struct BuybackBurnEvent {
+    pub burn_config: Pubkey,
+    pub executor: Pubkey,
+    pub swap_program: Pubkey,
+    pub min_amount_out: u64,
+    /// Tokens the swap delivered; all but `protocol_fee` were burned
+    pub received: u64,
+    /// Part of `received` sent to the fee destination instead of burned
+    pub protocol_fee: u64,
+    pub total_burned: u64,
+    pub supply_before: u64,
+    pub supply_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapProgramAdded {
+    pub burn_config: Pubkey,
+    pub swap_program: Pubkey,
+}
+
+#[event]
+pub struct SwapProgramRemoved {
+    pub burn_config: Pubkey,
+    pub swap_program: Pubkey,
+}
+
+#[event]
+pub struct TokenAccountClosed {
+    pub burn_config: Pubkey,
+    pub token_account: Pubkey,
+    pub executor: Pubkey,
+    pub burned: u64,
+    /// Part of the balance sent to the fee destination instead of burned
+    pub protocol_fee: u64,
+    /// Lamports sent to `destination`
+    pub rent_reclaimed: u64,
+    pub destination: Pubkey,
+    pub was_vault: bool,
+    pub total_burned: u64,
+    pub supply_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreditsDeposited {
+    pub credit_account: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct CreditsWithdrawn {
+    pub credit_account: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct LowCreditBalance {
+    pub burn_config: Pubkey,
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub threshold: u64,
+}
+
+#[event]
+pub struct BurnConfigMigrated {
+    pub legacy_config: Pubkey,
+    pub burn_config: Pubkey,
+    pub authority: Pubkey,
+    pub config_id: u64,
+}
+
+/// `burn_config` is `None` when the program-wide switch changed
+#[event]
+pub struct BurnsPaused {
+    pub burn_config: Option<Pubkey>,
+    pub by: Pubkey,
+    pub auto_tripped: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BurnsUnpaused {
+    pub burn_config: Option<Pubkey>,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OperatorAdded {
+    pub burn_config: Pubkey,
+    pub operator: Pubkey,
+    pub allowance: u64,
+    pub expires_at: i64,
+    pub permissions: u8,
+}
+
+#[event]
+pub struct OperatorRemoved {
+    pub burn_config: Pubkey,
+    pub operator: Pubkey,
+}
+
+#[event]
+pub struct ScheduledBurnCranked {
+    pub burn_config: Pubkey,
+    pub cranker: Pubkey,
+    pub amount: u64,
+    /// Lamports paid to the cranker
+    pub reward: u64,
+    pub tick: u64,
+    pub next_tick_at: i64,
+    pub total_burned: u64,
+    pub supply_before: u64,
+    pub supply_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BurnOrderProgress {
+    pub burn_order: Pubkey,
+    pub tranches_executed: u16,
+    pub tranche_count: u16,
+    /// Tokens burned by this tranche (0 when the order is created)
+    pub tranche_amount: u64,
+    pub burned: u64,
+    pub remaining: u64,
+    pub next_tranche_at: Option<i64>,
+}
+
+#[event]
+pub struct BurnOrderCancelled {
+    pub burn_order: Pubkey,
+    pub tranches_executed: u16,
+    pub burned: u64,
+    /// Unburned tokens returned to the authority's destination
+    pub released: u64,
+}
+
+#[event]
+pub struct GuardianAdded {
+    pub burn_config: Pubkey,
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct GuardianRemoved {
+    pub burn_config: Pubkey,
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub burn_config: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferAccepted {
+    pub burn_config: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub burn_config: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
     pub timestamp: i64,
 }
 
@@ -237,4 +4758,148 @@ pub enum ErrorCode {
     ProfitThresholdNotMet,
     #[msg("Insufficient burn amount based on profit")]
     InsufficientBurnAmount,
+    #[msg("x402 payment transfer not found in transaction")]
+    PaymentNotFound,
+    #[msg("x402 payment below required amount")]
+    InsufficientPayment,
+    #[msg("No profit report signed by the profit oracle found in transaction")]
+    ProfitAttestationMissing,
+    #[msg("Profit report has expired")]
+    ProfitAttestationExpired,
+    #[msg("Profit report period already used by an earlier burn")]
+    ProfitPeriodAlreadyUsed,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("Executor is neither the config authority nor a registered operator")]
+    UnauthorizedExecutor,
+    #[msg("Burn operator has expired")]
+    OperatorExpired,
+    #[msg("Burn operator lacks the required permission")]
+    OperatorPermissionDenied,
+    #[msg("Burn exceeds the operator's remaining allowance")]
+    OperatorAllowanceExceeded,
+    #[msg("Unknown operator permission bits")]
+    InvalidOperatorPermissions,
+    #[msg("Executor is neither owner nor approved delegate of the token account")]
+    InvalidBurnSource,
+    #[msg("Burn exceeds the maximum amount per burn")]
+    BurnExceedsMaxAmount,
+    #[msg("Burn exceeds the cap for the current burn window")]
+    WindowBurnCapExceeded,
+    #[msg("Minimum interval between burns has not elapsed")]
+    BurnCooldownActive,
+    #[msg("Burn exceeds the maximum share of mint supply")]
+    BurnExceedsSupplyShare,
+    #[msg("Invalid burn limits")]
+    InvalidBurnLimits,
+    #[msg("Burns are paused for this config")]
+    ConfigPaused,
+    #[msg("Burns are paused program-wide")]
+    ProgramPaused,
+    #[msg("Signer is not the program upgrade authority")]
+    NotUpgradeAuthority,
+    #[msg("Account is not a valid legacy mint-only burn config")]
+    InvalidLegacyConfig,
+    #[msg("Signer is not the config authority")]
+    NotConfigAuthority,
+    #[msg("Payment accounts are required when the program collects the x402 fee")]
+    PaymentAccountsMissing,
+    #[msg("Payment account does not match the burn config")]
+    InvalidPaymentAccount,
+    #[msg("A credit account is required when burns are paid with prepaid credits")]
+    CreditAccountMissing,
+    #[msg("Insufficient prepaid credits")]
+    InsufficientCredits,
+    #[msg("Fee must be between 0 and 10000 basis points")]
+    InvalidFeeBps,
+    #[msg("A fee token account is required when a protocol fee is due")]
+    FeeAccountMissing,
+    #[msg("Fee token account must belong to the fee destination and hold the burned mint")]
+    InvalidFeeAccount,
+    #[msg("Price feed account is required when the config is oracle-priced")]
+    PriceFeedMissing,
+    #[msg("Price feed account is not the configured Pyth price account or is not trading")]
+    InvalidPriceFeed,
+    #[msg("Price feed is older than the configured maximum age")]
+    StalePrice,
+    #[msg("Price confidence interval is wider than allowed")]
+    PriceConfidenceTooWide,
+    #[msg("Profit conversion overflowed")]
+    PriceConversionOverflow,
+    #[msg("Price feed needs a positive max age and a confidence limit of at most 10000 bps")]
+    InvalidPriceFeedSettings,
+    #[msg("ROI report must have a non-zero cost basis")]
+    InvalidRoiReport,
+    #[msg("No profit above the high-water mark left to burn")]
+    NoProfitAboveHighWaterMark,
+    #[msg("Cumulative profit overflowed")]
+    ProfitLedgerOverflow,
+    #[msg("PnL ledger account is required when profit comes from the ledger")]
+    PnlLedgerMissing,
+    #[msg("No unconsumed trades in the PnL ledger")]
+    NoLedgerTrades,
+    #[msg("Trade quote mint does not match the PnL ledger")]
+    InvalidQuoteMint,
+    #[msg("Signed AI decision for this burn not found in transaction")]
+    AiDecisionMissing,
+    #[msg("AI decision has expired")]
+    AiDecisionExpired,
+    #[msg("AI decision did not approve the burn")]
+    AiDecisionRejected,
+    #[msg("AI confidence is below the configured minimum")]
+    AiConfidenceTooLow,
+    #[msg("AI sentiment is not positive")]
+    AiSentimentNotPositive,
+    #[msg("Burn amount exceeds the AI decision's max_amount")]
+    AiDecisionAmountExceeded,
+    #[msg("AI confidence threshold must be between 0 and 100")]
+    InvalidAiPolicy,
+    #[msg("Burn needs more guardian signatures")]
+    QuorumNotMet,
+    #[msg("Guardian is already on the roster")]
+    GuardianAlreadyAdded,
+    #[msg("Guardian is not on the roster")]
+    GuardianNotFound,
+    #[msg("Guardian roster is full")]
+    TooManyGuardians,
+    #[msg("Quorum must be reachable by the roster and non-zero when a threshold is set")]
+    InvalidQuorum,
+    #[msg("Config has no burn vault")]
+    BurnVaultMissing,
+    #[msg("Schedule needs a positive interval, a non-zero tick amount and an end after its start")]
+    InvalidBurnSchedule,
+    #[msg("Next scheduled burn is not due yet")]
+    ScheduleTickNotDue,
+    #[msg("Burn schedule has ended")]
+    ScheduleEnded,
+    #[msg("Order needs a positive amount of at least one token per tranche and a non-negative interval")]
+    InvalidBurnOrder,
+    #[msg("Next tranche is not due yet")]
+    TrancheNotDue,
+    #[msg("All tranches of this order have been burned")]
+    BurnOrderComplete,
+    #[msg("Only the token account owner can burn everything and close it")]
+    CloseRequiresOwner,
+    #[msg("Swap program is not on the config's allowlist")]
+    SwapProgramNotAllowed,
+    #[msg("Swap program is already on the allowlist")]
+    SwapProgramAlreadyAdded,
+    #[msg("Swap program is not on the allowlist")]
+    SwapProgramNotFound,
+    #[msg("Swap program allowlist is full")]
+    TooManySwapPrograms,
+    #[msg("Swap delivered fewer tokens than min_amount_out")]
+    SlippageExceeded,
+    #[msg("A payment receipt is required in VerifiedTransfer mode")]
+    PaymentReceiptMissing,
+    #[msg("PnL ledger is full of unconsumed trades")]
+    PnlLedgerFull,
+    #[msg("Scheduled burn reaches the guardian quorum threshold")]
+    ScheduledBurnNeedsQuorum,
+    #[msg("Minimum swap output must be greater than zero")]
+    InvalidMinAmountOut,
+    #[msg("x402-verified burns cannot be invoked through CPI")]
+    PaymentRequiresTopLevel,
 }
@@ -0,0 +1,129 @@
+//! AI approval: burns need a decision signed by the config's `ai_signer` that
+//! meets its confidence and sentiment policy, and each decision is single-use.
+
+mod common;
+
+use common::*;
+use gigabrain_burn::{ErrorCode, Sentiment};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+const MIN_AI_CONFIDENCE: u8 = 80;
+
+async fn setup_ai_policy() -> (ProgramTestContext, BurnFixture, Keypair) {
+    let (mut context, fixture) = setup().await;
+    let ai_signer = Keypair::new();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetAiPolicy {
+                ai_signer: Some(ai_signer.pubkey()),
+                min_ai_confidence: MIN_AI_CONFIDENCE,
+                require_positive_sentiment: true,
+            },
+        )
+        .await
+        .unwrap();
+    (context, fixture, ai_signer)
+}
+
+#[tokio::test]
+async fn rejects_burn_without_ai_decision() {
+    let (mut context, fixture, _) = setup_ai_policy().await;
+    let authority = context.payer.pubkey();
+
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-no-decision");
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::AiDecisionMissing);
+}
+
+#[tokio::test]
+async fn rejects_low_confidence_decision() {
+    let (mut context, fixture, ai_signer) = setup_ai_policy().await;
+    let authority = context.payer.pubkey();
+
+    let mut instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-low-confidence");
+    instructions.insert(
+        0,
+        fixture.ai_attestation(
+            &ai_signer,
+            0,
+            AMOUNT,
+            MIN_AI_CONFIDENCE - 1,
+            Sentiment::Positive,
+        ),
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::AiConfidenceTooLow);
+}
+
+#[tokio::test]
+async fn rejects_non_positive_sentiment() {
+    let (mut context, fixture, ai_signer) = setup_ai_policy().await;
+    let authority = context.payer.pubkey();
+
+    let mut instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-neutral");
+    instructions.insert(
+        0,
+        fixture.ai_attestation(&ai_signer, 0, AMOUNT, MIN_AI_CONFIDENCE, Sentiment::Neutral),
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::AiSentimentNotPositive);
+}
+
+#[tokio::test]
+async fn decision_approves_a_single_burn() {
+    let (mut context, fixture, ai_signer) = setup_ai_policy().await;
+    let authority = context.payer.pubkey();
+    let decision = fixture.ai_attestation(
+        &ai_signer,
+        0,
+        AMOUNT,
+        MIN_AI_CONFIDENCE,
+        Sentiment::Positive,
+    );
+
+    let mut instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-approved");
+    instructions.insert(0, decision.clone());
+    process(&mut context, &instructions, &[]).await.unwrap();
+    assert_eq!(fixture.config(&mut context).await.ai_decision_nonce, 1);
+
+    // Replaying the consumed decision finds nothing for nonce 1
+    let mut instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 2, "x402-replayed");
+    instructions.insert(0, decision);
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::AiDecisionMissing);
+}
+
+#[tokio::test]
+async fn rejects_burn_above_decision_amount() {
+    let (mut context, fixture, ai_signer) = setup_ai_policy().await;
+    let authority = context.payer.pubkey();
+
+    // Approved for one token less than the burn takes
+    let mut instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-oversized");
+    instructions.insert(
+        0,
+        fixture.ai_attestation(
+            &ai_signer,
+            0,
+            AMOUNT - 1,
+            MIN_AI_CONFIDENCE,
+            Sentiment::Positive,
+        ),
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::AiDecisionAmountExceeded);
+}
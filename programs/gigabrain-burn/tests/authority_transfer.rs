@@ -0,0 +1,151 @@
+//! Two-step authority transfer: the proposed wallet must accept before it
+//! controls the config, and the current authority can withdraw the proposal.
+
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::ErrorCode;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+fn accept_instruction(fixture: &BurnFixture, pending_authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::AcceptAuthority {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            pending_authority: *pending_authority,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::AcceptAuthority {}.data(),
+    }
+}
+
+fn propose_instruction(
+    fixture: &BurnFixture,
+    authority: &Pubkey,
+    new_authority: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::UpdateBurnConfig {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::ProposeAuthority { new_authority }.data(),
+    }
+}
+
+#[tokio::test]
+async fn hands_off_authority_in_two_steps() {
+    let (mut context, fixture) = setup().await;
+    let previous_authority = context.payer.pubkey();
+    let new_authority = Keypair::new();
+
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::ProposeAuthority {
+                new_authority: new_authority.pubkey(),
+            },
+        )
+        .await
+        .unwrap();
+    // Proposing alone does not move control
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.authority, previous_authority);
+    assert_eq!(config.pending_authority, Some(new_authority.pubkey()));
+
+    let accept = accept_instruction(&fixture, &new_authority.pubkey());
+    process(&mut context, &[accept], &[&new_authority])
+        .await
+        .unwrap();
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.authority, new_authority.pubkey());
+    assert_eq!(config.pending_authority, None);
+
+    // The previous authority is locked out, the new one is in control
+    let result = fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::ProposeAuthority {
+                new_authority: previous_authority,
+            },
+        )
+        .await;
+    assert_anchor_error(result, anchor_lang::error::ErrorCode::ConstraintHasOne);
+    let propose = propose_instruction(&fixture, &new_authority.pubkey(), previous_authority);
+    process(&mut context, &[propose], &[&new_authority])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rejects_accept_by_another_wallet() {
+    let (mut context, fixture) = setup().await;
+    let new_authority = Keypair::new();
+    let intruder = Keypair::new();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::ProposeAuthority {
+                new_authority: new_authority.pubkey(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let accept = accept_instruction(&fixture, &intruder.pubkey());
+    let result = process(&mut context, &[accept], &[&intruder]).await;
+
+    assert_program_error(result, ErrorCode::NotPendingAuthority);
+    assert_eq!(
+        fixture.config(&mut context).await.authority,
+        context.payer.pubkey()
+    );
+}
+
+#[tokio::test]
+async fn cancel_clears_pending_authority() {
+    let (mut context, fixture) = setup().await;
+    let new_authority = Keypair::new();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::ProposeAuthority {
+                new_authority: new_authority.pubkey(),
+            },
+        )
+        .await
+        .unwrap();
+
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::CancelAuthorityTransfer {},
+        )
+        .await
+        .unwrap();
+    assert_eq!(fixture.config(&mut context).await.pending_authority, None);
+
+    // The withdrawn proposal can no longer be accepted
+    let accept = accept_instruction(&fixture, &new_authority.pubkey());
+    let result = process(&mut context, &[accept], &[&new_authority]).await;
+    assert_program_error(result, ErrorCode::NotPendingAuthority);
+
+    // Same transaction as the first cancel
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::CancelAuthorityTransfer {},
+        )
+        .await;
+    assert_program_error(result, ErrorCode::NoPendingAuthority);
+}
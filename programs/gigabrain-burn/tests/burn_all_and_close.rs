@@ -0,0 +1,262 @@
+//! Retiring token accounts: burn the whole balance, close the account and
+//! send its rent to the chosen destination, under the quorum and AI policy
+//! and for the same x402 payment as any other burn.
+
+mod common;
+
+use common::*;
+use gigabrain_burn::{ErrorCode, Sentiment};
+use solana_program_test::BanksClientError;
+use solana_sdk::{
+    instruction::{AccountMeta, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction::SystemError,
+    transaction::TransactionError,
+};
+
+const VAULT_BALANCE: u64 = 10_000_000;
+
+#[tokio::test]
+async fn burns_and_closes_owned_account() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let destination = Pubkey::new_unique();
+    let rent = context
+        .banks_client
+        .get_balance(fixture.token_account)
+        .await
+        .unwrap();
+    let supply_before = mint_supply(&mut context, &fixture.token_mint).await;
+
+    let instructions = fixture.close_instructions(
+        &authority,
+        &fixture.token_account,
+        &destination,
+        "x402-close",
+    );
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(fixture.token_account)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context.banks_client.get_balance(destination).await.unwrap(),
+        rent
+    );
+    assert_eq!(
+        mint_supply(&mut context, &fixture.token_mint).await,
+        supply_before - INITIAL_BALANCE
+    );
+    assert_eq!(
+        fixture.config(&mut context).await.total_burned,
+        INITIAL_BALANCE
+    );
+}
+
+#[tokio::test]
+async fn burns_and_closes_vault() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let vault = fixture.create_vault(&mut context, VAULT_BALANCE).await;
+    let destination = Pubkey::new_unique();
+    let rent = context.banks_client.get_balance(vault).await.unwrap();
+
+    let instructions =
+        fixture.close_instructions(&authority, &vault, &destination, "x402-close-vault");
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(vault)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context.banks_client.get_balance(destination).await.unwrap(),
+        rent
+    );
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.vault, None);
+    assert_eq!(config.total_burned, VAULT_BALANCE);
+}
+
+#[tokio::test]
+async fn large_balance_needs_guardian_quorum() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let guardian = Keypair::new();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::AddGuardian {
+                guardian: guardian.pubkey(),
+            },
+        )
+        .await
+        .unwrap();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetGuardianQuorum {
+                guardian_quorum: 1,
+                quorum_burn_threshold: INITIAL_BALANCE,
+            },
+        )
+        .await
+        .unwrap();
+    let destination = Pubkey::new_unique();
+
+    let mut instructions = fixture.close_instructions(
+        &authority,
+        &fixture.token_account,
+        &destination,
+        "x402-quorum",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::QuorumNotMet);
+
+    instructions[1]
+        .accounts
+        .push(AccountMeta::new_readonly(guardian.pubkey(), true));
+    process(&mut context, &instructions, &[&guardian])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn needs_ai_decision_when_configured() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let ai_signer = Keypair::new();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetAiPolicy {
+                ai_signer: Some(ai_signer.pubkey()),
+                min_ai_confidence: 0,
+                require_positive_sentiment: false,
+            },
+        )
+        .await
+        .unwrap();
+    let destination = Pubkey::new_unique();
+
+    let mut instructions =
+        fixture.close_instructions(&authority, &fixture.token_account, &destination, "x402-ai");
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::AiDecisionMissing);
+
+    let decision = fixture.ai_attestation(&ai_signer, 0, INITIAL_BALANCE, 90, Sentiment::Neutral);
+    instructions.insert(0, decision);
+    process(&mut context, &instructions, &[]).await.unwrap();
+    assert_eq!(fixture.config(&mut context).await.ai_decision_nonce, 1);
+}
+
+#[tokio::test]
+async fn rejects_retirement_without_payment() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let destination = Pubkey::new_unique();
+
+    let accounts = fixture.close_accounts(
+        &authority,
+        &fixture.token_account,
+        &destination,
+        "x402-unpaid",
+    );
+    let result = process(
+        &mut context,
+        &[close_instruction(accounts, "x402-unpaid")],
+        &[],
+    )
+    .await;
+
+    assert_program_error(result, ErrorCode::PaymentNotFound);
+}
+
+#[tokio::test]
+async fn every_retirement_pays() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let destination = Pubkey::new_unique();
+    let instructions = fixture.close_instructions(
+        &authority,
+        &fixture.token_account,
+        &destination,
+        "x402-once",
+    );
+    process(&mut context, &instructions, &[]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &fixture.payment_treasury).await,
+        PAYMENT_AMOUNT
+    );
+
+    // Funding a fresh account to retire again needs a fresh payment
+    let refill = create_token_account(
+        &mut context,
+        &fixture.token_program,
+        &fixture.token_mint,
+        &[],
+        &authority,
+        VAULT_BALANCE,
+    )
+    .await;
+    let instructions = fixture.close_instructions(&authority, &refill, &destination, "x402-once");
+    let result = process(&mut context, &instructions, &[]).await;
+    assert!(matches!(
+        result,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(1, InstructionError::Custom(code))
+        )) if code == SystemError::AccountAlreadyInUse as u32
+    ));
+
+    let instructions = fixture.close_instructions(&authority, &refill, &destination, "x402-twice");
+    process(&mut context, &instructions, &[]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &fixture.payment_treasury).await,
+        2 * PAYMENT_AMOUNT
+    );
+    assert_eq!(
+        fixture.config(&mut context).await.total_burned,
+        INITIAL_BALANCE + VAULT_BALANCE
+    );
+}
+
+#[tokio::test]
+async fn closes_empty_account_without_payment() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let empty = create_token_account(
+        &mut context,
+        &fixture.token_program,
+        &fixture.token_mint,
+        &[],
+        &authority,
+        0,
+    )
+    .await;
+    let destination = Pubkey::new_unique();
+
+    let accounts = gigabrain_burn::accounts::BurnAllAndClose {
+        payment_receipt: None,
+        ..fixture.close_accounts(&authority, &empty, &destination, "x402-empty")
+    };
+    process(
+        &mut context,
+        &[close_instruction(accounts, "x402-empty")],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(empty)
+        .await
+        .unwrap()
+        .is_none());
+}
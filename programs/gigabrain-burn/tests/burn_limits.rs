@@ -0,0 +1,122 @@
+//! On-chain burn caps: per-burn maximum, share of supply, rolling window
+//! and cooldown.
+
+mod common;
+
+use common::*;
+use gigabrain_burn::ErrorCode;
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::signature::Signer;
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+/// Smallest burn `PROFIT_AMOUNT` requires
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+const WINDOW_SECONDS: i64 = 3600;
+const MIN_BURN_INTERVAL: i64 = 60;
+
+async fn setup_limits(
+    max_burn_amount: u64,
+    window_seconds: i64,
+    window_max_burned: u64,
+    min_burn_interval: i64,
+    max_supply_bps: u16,
+) -> (ProgramTestContext, BurnFixture) {
+    let (mut context, fixture) = setup().await;
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetBurnLimits {
+                max_burn_amount,
+                window_seconds,
+                window_max_burned,
+                min_burn_interval,
+                max_supply_bps,
+            },
+        )
+        .await
+        .unwrap();
+    (context, fixture)
+}
+
+/// Burn `amount` against a fresh profit period.
+async fn burn(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    amount: u64,
+    period_id: u64,
+    x402_signature: &str,
+) -> Result<(), BanksClientError> {
+    let authority = context.payer.pubkey();
+    let instructions =
+        fixture.burn_instructions(&authority, amount, PROFIT_AMOUNT, period_id, x402_signature);
+    process(context, &instructions, &[]).await
+}
+
+#[tokio::test]
+async fn rejects_burn_above_max_amount() {
+    let (mut context, fixture) = setup_limits(AMOUNT, 0, 0, 0, 0).await;
+
+    let result = burn(&mut context, &fixture, AMOUNT + 1, 1, "x402-over-max").await;
+    assert_program_error(result, ErrorCode::BurnExceedsMaxAmount);
+
+    burn(&mut context, &fixture, AMOUNT, 1, "x402-at-max")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rejects_burn_above_supply_share() {
+    // 1% of the initial supply is exactly `AMOUNT`
+    let (mut context, fixture) = setup_limits(0, 0, 0, 0, 100).await;
+    assert_eq!(INITIAL_BALANCE / 100, AMOUNT);
+
+    let result = burn(&mut context, &fixture, AMOUNT + 1, 1, "x402-over-share").await;
+    assert_program_error(result, ErrorCode::BurnExceedsSupplyShare);
+
+    burn(&mut context, &fixture, AMOUNT, 1, "x402-at-share")
+        .await
+        .unwrap();
+    assert_eq!(
+        mint_supply(&mut context, &fixture.token_mint).await,
+        INITIAL_BALANCE - AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn caps_burns_per_window_until_it_resets() {
+    let (mut context, fixture) = setup_limits(0, WINDOW_SECONDS, 2 * AMOUNT, 0, 0).await;
+
+    burn(&mut context, &fixture, AMOUNT, 1, "x402-window-1")
+        .await
+        .unwrap();
+    burn(&mut context, &fixture, AMOUNT, 2, "x402-window-2")
+        .await
+        .unwrap();
+    let result = burn(&mut context, &fixture, AMOUNT, 3, "x402-window-full").await;
+    assert_program_error(result, ErrorCode::WindowBurnCapExceeded);
+
+    advance_clock(&mut context, WINDOW_SECONDS).await;
+    burn(&mut context, &fixture, AMOUNT, 3, "x402-window-reset")
+        .await
+        .unwrap();
+
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.window_burned, AMOUNT);
+    assert_eq!(config.total_burned, 3 * AMOUNT);
+}
+
+#[tokio::test]
+async fn enforces_cooldown_between_burns() {
+    let (mut context, fixture) = setup_limits(0, 0, 0, MIN_BURN_INTERVAL, 0).await;
+
+    burn(&mut context, &fixture, AMOUNT, 1, "x402-cooldown-1")
+        .await
+        .unwrap();
+    let result = burn(&mut context, &fixture, AMOUNT, 2, "x402-cooldown-early").await;
+    assert_program_error(result, ErrorCode::BurnCooldownActive);
+
+    advance_clock(&mut context, MIN_BURN_INTERVAL).await;
+    burn(&mut context, &fixture, AMOUNT, 2, "x402-cooldown-2")
+        .await
+        .unwrap();
+}
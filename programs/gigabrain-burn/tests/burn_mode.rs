@@ -0,0 +1,264 @@
+//! Burn sizing: `Amount` must meet the config minimum, `BalanceBps` resolves
+//! against the source balance at execution time and may not exceed it, and
+//! `All` empties the source, closing it unless it is the burn vault.
+
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::{BurnMode, BurnOperator, ErrorCode};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+
+/// Register a funded burn operator, so the executor is not also the payer
+async fn add_operator(context: &mut ProgramTestContext, fixture: &BurnFixture) -> Keypair {
+    let operator = Keypair::new();
+    let authority = context.payer.pubkey();
+    let instructions = [
+        system_instruction::transfer(&authority, &operator.pubkey(), 1_000_000_000),
+        Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::AddBurnOperator {
+                burn_config: fixture.burn_config,
+                token_mint: fixture.token_mint,
+                burn_operator: burn_operator_address(&fixture.burn_config, &operator.pubkey()),
+                authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: gigabrain_burn::instruction::AddBurnOperator {
+                operator: operator.pubkey(),
+                allowance: u64::MAX,
+                expires_at: i64::MAX,
+                permissions: BurnOperator::PERMISSION_BURN,
+            }
+            .data(),
+        },
+    ];
+    process(context, &instructions, &[]).await.unwrap();
+    operator
+}
+
+/// Attested, paid `BurnMode::All` burn by `operator` from `token_account`.
+fn burn_all_instructions(
+    fixture: &BurnFixture,
+    authority: &Pubkey,
+    operator: &Pubkey,
+    token_account: &Pubkey,
+    x402_signature: &str,
+) -> Vec<Instruction> {
+    vec![
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        fixture.payment_instruction(authority, PAYMENT_AMOUNT),
+        fixture.execute_instruction(
+            operator,
+            authority,
+            Some(burn_operator_address(&fixture.burn_config, operator)),
+            token_account,
+            BurnMode::All,
+            x402_signature,
+        ),
+    ]
+}
+
+async fn lamports(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .map_or(0, |account| account.lamports)
+}
+
+#[tokio::test]
+async fn amount_must_meet_min_burn_amount() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let min_burn_amount = 2 * AMOUNT;
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::UpdateBurnConfig {
+                new_profit_threshold: None,
+                new_burn_percentage: None,
+                new_min_burn_amount: Some(min_burn_amount),
+                new_payment_amount: None,
+                new_profit_oracle: None,
+                new_auto_pause_bps: None,
+                new_payment_mode: None,
+                new_profit_mode: None,
+                new_drawdown_reset_bps: None,
+                new_profit_source: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    // Enough for the profit, not for the config minimum
+    let instructions = fixture.burn_instructions(
+        &authority,
+        min_burn_amount - 1,
+        PROFIT_AMOUNT,
+        1,
+        "x402-below-min",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::BelowMinBurnAmount);
+
+    let instructions =
+        fixture.burn_instructions(&authority, min_burn_amount, PROFIT_AMOUNT, 1, "x402-at-min");
+    process(&mut context, &instructions, &[]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE - min_burn_amount
+    );
+}
+
+#[tokio::test]
+async fn burns_share_of_balance() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let instructions = fixture.burn_instructions_with_mode(
+        &authority,
+        BurnMode::BalanceBps { bps: 100 },
+        PROFIT_AMOUNT,
+        1,
+        "x402-share",
+    );
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE - INITIAL_BALANCE / 100
+    );
+}
+
+#[tokio::test]
+async fn rejects_share_above_whole_balance() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let instructions = fixture.burn_instructions_with_mode(
+        &authority,
+        BurnMode::BalanceBps { bps: 10_001 },
+        PROFIT_AMOUNT,
+        1,
+        "x402-over-share",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::InvalidBurnPercentage);
+}
+
+#[tokio::test]
+async fn all_closes_source_and_refunds_executor() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let operator = add_operator(&mut context, &fixture).await;
+    let source = create_token_account(
+        &mut context,
+        &fixture.token_program,
+        &fixture.token_mint,
+        &[],
+        &operator.pubkey(),
+        2 * AMOUNT,
+    )
+    .await;
+    let source_rent = lamports(&mut context, &source).await;
+    let operator_before = lamports(&mut context, &operator.pubkey()).await;
+    let supply_before = mint_supply(&mut context, &fixture.token_mint).await;
+
+    let instructions = burn_all_instructions(
+        &fixture,
+        &authority,
+        &operator.pubkey(),
+        &source,
+        "x402-all",
+    );
+    process(&mut context, &instructions, &[&operator])
+        .await
+        .unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(source)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        lamports(&mut context, &operator.pubkey()).await,
+        operator_before + source_rent
+    );
+    assert_eq!(
+        mint_supply(&mut context, &fixture.token_mint).await,
+        supply_before - 2 * AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn all_empties_vault_without_closing_it() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let vault = fixture.create_vault(&mut context, 2 * AMOUNT).await;
+
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        fixture.payment_instruction(&authority, PAYMENT_AMOUNT),
+        fixture.execute_instruction(
+            &authority,
+            &authority,
+            None,
+            &vault,
+            BurnMode::All,
+            "x402-all-vault",
+        ),
+    ];
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    assert_eq!(token_balance(&mut context, &vault).await, 0);
+    assert_eq!(fixture.config(&mut context).await.vault, Some(vault));
+}
+
+#[tokio::test]
+async fn all_rejects_delegated_source() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let operator = add_operator(&mut context, &fixture).await;
+    let approve = spl_token_2022::instruction::approve_checked(
+        &fixture.token_program,
+        &fixture.token_account,
+        &fixture.token_mint,
+        &operator.pubkey(),
+        &authority,
+        &[],
+        INITIAL_BALANCE,
+        DECIMALS,
+    )
+    .unwrap();
+    process(&mut context, &[approve], &[]).await.unwrap();
+
+    // A delegate may burn everything, but only the owner may close the account
+    let instructions = burn_all_instructions(
+        &fixture,
+        &authority,
+        &operator.pubkey(),
+        &fixture.token_account,
+        "x402-all-delegated",
+    );
+    let result = process(&mut context, &instructions, &[&operator]).await;
+
+    assert_program_error(result, ErrorCode::CloseRequiresOwner);
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE
+    );
+}
@@ -0,0 +1,330 @@
+//! Burn operators: agent keys that burn within an allowance, an expiry and a
+//! permission mask, from their own accounts or as an SPL delegate.
+
+mod common;
+
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::{BurnOperator, ErrorCode};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+const LIFETIME: i64 = 3_600;
+
+async fn add_operator(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    operator: &Pubkey,
+    allowance: u64,
+    permissions: u8,
+) {
+    let now = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let instruction = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::AddBurnOperator {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            burn_operator: burn_operator_address(&fixture.burn_config, operator),
+            authority: context.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::AddBurnOperator {
+            operator: *operator,
+            allowance,
+            expires_at: now + LIFETIME,
+            permissions,
+        }
+        .data(),
+    };
+    process(context, &[instruction], &[]).await.unwrap();
+}
+
+async fn operator_account(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    operator: &Pubkey,
+) -> BurnOperator {
+    let account = context
+        .banks_client
+        .get_account(burn_operator_address(&fixture.burn_config, operator))
+        .await
+        .unwrap()
+        .unwrap();
+    BurnOperator::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+/// An attested burn of `amount` by `operator` from `token_account`, paid for
+/// by the config authority.
+async fn operator_burn(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    operator: &Keypair,
+    token_account: &Pubkey,
+    period_id: u64,
+    x402_signature: &str,
+) -> Result<(), BanksClientError> {
+    let authority = context.payer.pubkey();
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, period_id)),
+        fixture.payment_instruction(&authority, PAYMENT_AMOUNT),
+        fixture.operator_burn_instruction(
+            &operator.pubkey(),
+            &authority,
+            token_account,
+            AMOUNT,
+            x402_signature,
+        ),
+    ];
+    process(context, &instructions, &[operator]).await
+}
+
+/// A token account owned by `owner` holding `amount` of the burn mint.
+async fn funded_account(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    create_token_account(
+        context,
+        &fixture.token_program,
+        &fixture.token_mint,
+        &[],
+        owner,
+        amount,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn decrements_allowance_until_exhausted() {
+    let (mut context, fixture) = setup().await;
+    let operator = Keypair::new();
+    add_operator(
+        &mut context,
+        &fixture,
+        &operator.pubkey(),
+        AMOUNT * 3 / 2,
+        BurnOperator::PERMISSION_BURN,
+    )
+    .await;
+    let source = funded_account(&mut context, &fixture, &operator.pubkey(), 3 * AMOUNT).await;
+
+    operator_burn(&mut context, &fixture, &operator, &source, 1, "x402-op-1")
+        .await
+        .unwrap();
+    assert_eq!(token_balance(&mut context, &source).await, 2 * AMOUNT);
+    assert_eq!(
+        operator_account(&mut context, &fixture, &operator.pubkey())
+            .await
+            .allowance,
+        AMOUNT / 2
+    );
+
+    let result = operator_burn(&mut context, &fixture, &operator, &source, 2, "x402-op-2").await;
+    assert_program_error(result, ErrorCode::OperatorAllowanceExceeded);
+}
+
+#[tokio::test]
+async fn rejects_expired_operator() {
+    let (mut context, fixture) = setup().await;
+    let operator = Keypair::new();
+    add_operator(
+        &mut context,
+        &fixture,
+        &operator.pubkey(),
+        u64::MAX,
+        BurnOperator::PERMISSION_BURN,
+    )
+    .await;
+    let source = funded_account(&mut context, &fixture, &operator.pubkey(), AMOUNT).await;
+
+    advance_clock(&mut context, LIFETIME).await;
+    let result = operator_burn(&mut context, &fixture, &operator, &source, 1, "x402-late").await;
+
+    assert_program_error(result, ErrorCode::OperatorExpired);
+}
+
+#[tokio::test]
+async fn rejects_operator_without_burn_permission() {
+    let (mut context, fixture) = setup().await;
+    let operator = Keypair::new();
+    add_operator(
+        &mut context,
+        &fixture,
+        &operator.pubkey(),
+        u64::MAX,
+        BurnOperator::PERMISSION_RECORD_TRADES,
+    )
+    .await;
+    let source = funded_account(&mut context, &fixture, &operator.pubkey(), AMOUNT).await;
+
+    let result = operator_burn(
+        &mut context,
+        &fixture,
+        &operator,
+        &source,
+        1,
+        "x402-no-perm",
+    )
+    .await;
+    assert_program_error(result, ErrorCode::OperatorPermissionDenied);
+
+    // Limited updates need their own bit too
+    let update = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::OperatorUpdateBurnConfig {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            burn_operator: burn_operator_address(&fixture.burn_config, &operator.pubkey()),
+            operator: operator.pubkey(),
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::OperatorUpdateBurnConfig {
+            new_profit_threshold: None,
+            new_min_burn_amount: Some(2 * MIN_BURN_AMOUNT),
+        }
+        .data(),
+    };
+    let result = process(&mut context, &[update], &[&operator]).await;
+    assert_program_error(result, ErrorCode::OperatorPermissionDenied);
+}
+
+#[tokio::test]
+async fn rejects_removed_operator() {
+    let (mut context, fixture) = setup().await;
+    let operator = Keypair::new();
+    add_operator(
+        &mut context,
+        &fixture,
+        &operator.pubkey(),
+        u64::MAX,
+        BurnOperator::PERMISSION_BURN,
+    )
+    .await;
+    let source = funded_account(&mut context, &fixture, &operator.pubkey(), AMOUNT).await;
+
+    let remove = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::RemoveBurnOperator {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            burn_operator: burn_operator_address(&fixture.burn_config, &operator.pubkey()),
+            authority: context.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::RemoveBurnOperator {}.data(),
+    };
+    process(&mut context, &[remove], &[]).await.unwrap();
+
+    let result = operator_burn(
+        &mut context,
+        &fixture,
+        &operator,
+        &source,
+        1,
+        "x402-removed",
+    )
+    .await;
+    assert_anchor_error(result, anchor_lang::error::ErrorCode::AccountNotInitialized);
+
+    // Leaving the operator account out makes it a plain stranger
+    let authority = context.payer.pubkey();
+    let burn_operator = burn_operator_address(&fixture.burn_config, &operator.pubkey());
+    let mut burn = fixture.operator_burn_instruction(
+        &operator.pubkey(),
+        &authority,
+        &source,
+        AMOUNT,
+        "x402-stranger",
+    );
+    for meta in burn
+        .accounts
+        .iter_mut()
+        .filter(|meta| meta.pubkey == burn_operator)
+    {
+        *meta = AccountMeta::new_readonly(gigabrain_burn::ID, false);
+    }
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        fixture.payment_instruction(&authority, PAYMENT_AMOUNT),
+        burn,
+    ];
+    let result = process(&mut context, &instructions, &[&operator]).await;
+    assert_program_error(result, ErrorCode::UnauthorizedExecutor);
+}
+
+#[tokio::test]
+async fn burns_from_delegated_source() {
+    let (mut context, fixture) = setup().await;
+    let operator = Keypair::new();
+    add_operator(
+        &mut context,
+        &fixture,
+        &operator.pubkey(),
+        u64::MAX,
+        BurnOperator::PERMISSION_BURN,
+    )
+    .await;
+
+    // The authority approves the operator to spend one burn's worth
+    let approve = spl_token_2022::instruction::approve_checked(
+        &fixture.token_program,
+        &fixture.token_account,
+        &fixture.token_mint,
+        &operator.pubkey(),
+        &context.payer.pubkey(),
+        &[],
+        AMOUNT,
+        DECIMALS,
+    )
+    .unwrap();
+    process(&mut context, &[approve], &[]).await.unwrap();
+
+    operator_burn(
+        &mut context,
+        &fixture,
+        &operator,
+        &fixture.token_account,
+        1,
+        "x402-delegate",
+    )
+    .await
+    .unwrap();
+    let account = context
+        .banks_client
+        .get_account(fixture.token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let account = spl_token::state::Account::unpack(&account.data).unwrap();
+    assert_eq!(account.amount, INITIAL_BALANCE - AMOUNT);
+    assert_eq!(account.delegated_amount, 0);
+
+    // The approval is used up
+    let result = operator_burn(
+        &mut context,
+        &fixture,
+        &operator,
+        &fixture.token_account,
+        2,
+        "x402-delegate-again",
+    )
+    .await;
+    assert_program_error(result, ErrorCode::InvalidBurnSource);
+}
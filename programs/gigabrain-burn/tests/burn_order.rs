@@ -0,0 +1,313 @@
+//! Tranched burn orders: tranches run on their timetable with the remainder
+//! in the last one, cancelling releases the escrow, and large orders need the
+//! guardian quorum up front.
+
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::ErrorCode;
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+};
+use spl_token_2022::extension::ExtensionType;
+
+const ORDER_ID: u64 = 7;
+const TRANCHE_INTERVAL: i64 = 600;
+
+fn burn_order_address(burn_config: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"burn_order", burn_config.as_ref(), &ORDER_ID.to_le_bytes()],
+        &gigabrain_burn::ID,
+    )
+    .0
+}
+
+fn escrow_address(burn_order: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"burn_order_escrow", burn_order.as_ref()],
+        &gigabrain_burn::ID,
+    )
+    .0
+}
+
+/// Order `total_amount` in `tranche_count` tranches starting now, with
+/// `guardians` co-signing.
+async fn create_order(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    total_amount: u64,
+    tranche_count: u16,
+    guardians: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    create_order_every(
+        context,
+        fixture,
+        total_amount,
+        tranche_count,
+        TRANCHE_INTERVAL,
+        guardians,
+    )
+    .await
+}
+
+/// [`create_order`] with tranches `tranche_interval` seconds apart.
+async fn create_order_every(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    total_amount: u64,
+    tranche_count: u16,
+    tranche_interval: i64,
+    guardians: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let now = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let burn_order = burn_order_address(&fixture.burn_config);
+    let mut accounts = gigabrain_burn::accounts::CreateBurnOrder {
+        burn_config: fixture.burn_config,
+        token_mint: fixture.token_mint,
+        burn_order,
+        escrow: escrow_address(&burn_order),
+        source: fixture.token_account,
+        authority: context.payer.pubkey(),
+        token_program: fixture.token_program,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        guardians
+            .iter()
+            .map(|guardian| AccountMeta::new_readonly(guardian.pubkey(), true)),
+    );
+    let instruction = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts,
+        data: gigabrain_burn::instruction::CreateBurnOrder {
+            order_id: ORDER_ID,
+            total_amount,
+            tranche_count,
+            start_time: now,
+            tranche_interval,
+        }
+        .data(),
+    };
+    process(context, &[instruction], guardians).await
+}
+
+async fn crank(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+) -> Result<(), BanksClientError> {
+    // Cranks are otherwise identical transactions
+    context.get_new_latest_blockhash().await.unwrap();
+    let burn_order = burn_order_address(&fixture.burn_config);
+    let instruction = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::CrankBurnOrder {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            burn_order,
+            escrow: escrow_address(&burn_order),
+            global_config: global_config_address().0,
+            token_program: fixture.token_program,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::CrankBurnOrder {}.data(),
+    };
+    process(context, &[instruction], &[]).await
+}
+
+#[tokio::test]
+async fn burns_remainder_in_last_tranche() {
+    let (mut context, fixture) = setup().await;
+    let total_amount = 1_000_003;
+    create_order(&mut context, &fixture, total_amount, 3, &[])
+        .await
+        .unwrap();
+    let escrow = escrow_address(&burn_order_address(&fixture.burn_config));
+    let supply_before = mint_supply(&mut context, &fixture.token_mint).await;
+
+    crank(&mut context, &fixture).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &escrow).await,
+        total_amount - 333_334
+    );
+    let result = crank(&mut context, &fixture).await;
+    assert_program_error(result, ErrorCode::TrancheNotDue);
+
+    advance_clock(&mut context, TRANCHE_INTERVAL).await;
+    crank(&mut context, &fixture).await.unwrap();
+    advance_clock(&mut context, TRANCHE_INTERVAL).await;
+    crank(&mut context, &fixture).await.unwrap();
+
+    // 333_334 + 333_334 + 333_335
+    assert_eq!(token_balance(&mut context, &escrow).await, 0);
+    assert_eq!(
+        mint_supply(&mut context, &fixture.token_mint).await,
+        supply_before - total_amount
+    );
+
+    advance_clock(&mut context, TRANCHE_INTERVAL).await;
+    let result = crank(&mut context, &fixture).await;
+    assert_program_error(result, ErrorCode::BurnOrderComplete);
+}
+
+#[tokio::test]
+async fn cancel_releases_escrow() {
+    let (mut context, fixture) = setup().await;
+    create_order(&mut context, &fixture, 900_000, 3, &[])
+        .await
+        .unwrap();
+    crank(&mut context, &fixture).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE - 900_000
+    );
+
+    let burn_order = burn_order_address(&fixture.burn_config);
+    let escrow = escrow_address(&burn_order);
+    let cancel = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::CancelBurnOrder {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            burn_order,
+            escrow,
+            destination: fixture.token_account,
+            authority: context.payer.pubkey(),
+            token_program: fixture.token_program,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::CancelBurnOrder {}.data(),
+    };
+    process(&mut context, &[cancel], &[]).await.unwrap();
+
+    // The two unburned tranches come back; escrow and order are closed
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE - 300_000
+    );
+    assert!(context
+        .banks_client
+        .get_account(escrow)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(context
+        .banks_client
+        .get_account(burn_order)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn large_order_needs_guardian_quorum() {
+    let (mut context, fixture) = setup().await;
+    let guardian = Keypair::new();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::AddGuardian {
+                guardian: guardian.pubkey(),
+            },
+        )
+        .await
+        .unwrap();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetGuardianQuorum {
+                guardian_quorum: 1,
+                quorum_burn_threshold: 900_000,
+            },
+        )
+        .await
+        .unwrap();
+
+    let result = create_order(&mut context, &fixture, 900_000, 3, &[]).await;
+    assert_program_error(result, ErrorCode::QuorumNotMet);
+
+    create_order(&mut context, &fixture, 900_000, 3, &[&guardian])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rejects_order_without_interval() {
+    let (mut context, fixture) = setup().await;
+
+    let result = create_order_every(&mut context, &fixture, 900_000, 3, 0, &[]).await;
+
+    assert_program_error(result, ErrorCode::InvalidBurnOrder);
+}
+
+#[tokio::test]
+async fn rejects_empty_tranches_after_transfer_fee() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = BurnFixture::setup(
+        &mut context,
+        &spl_token_2022::ID,
+        &[ExtensionType::TransferFeeConfig],
+    )
+    .await;
+
+    // The 1% transfer fee leaves 99 in escrow for 100 tranches
+    let result = create_order(&mut context, &fixture, 100, 100, &[]).await;
+
+    assert_program_error(result, ErrorCode::InvalidBurnOrder);
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE
+    );
+}
+
+#[tokio::test]
+async fn tranches_respect_min_burn_amount() {
+    let (mut context, fixture) = setup().await;
+    let update_min_burn_amount = |min_burn_amount| gigabrain_burn::instruction::UpdateBurnConfig {
+        new_profit_threshold: None,
+        new_burn_percentage: None,
+        new_min_burn_amount: Some(min_burn_amount),
+        new_payment_amount: None,
+        new_profit_oracle: None,
+        new_auto_pause_bps: None,
+        new_payment_mode: None,
+        new_profit_mode: None,
+        new_drawdown_reset_bps: None,
+        new_profit_source: None,
+    };
+    fixture
+        .update(&mut context, update_min_burn_amount(400_000))
+        .await
+        .unwrap();
+
+    let result = create_order(&mut context, &fixture, 900_000, 3, &[]).await;
+    assert_program_error(result, ErrorCode::BelowMinBurnAmount);
+
+    // Raising the minimum later holds the order back like a scheduled burn
+    fixture
+        .update(&mut context, update_min_burn_amount(300_000))
+        .await
+        .unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+    create_order(&mut context, &fixture, 900_000, 3, &[])
+        .await
+        .unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+    fixture
+        .update(&mut context, update_min_burn_amount(400_000))
+        .await
+        .unwrap();
+    let result = crank(&mut context, &fixture).await;
+    assert_program_error(result, ErrorCode::BelowMinBurnAmount);
+}
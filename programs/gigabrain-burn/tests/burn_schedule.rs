@@ -0,0 +1,336 @@
+//! Recurring vault burns: ticks run only when due, missed ticks are skipped,
+//! nothing runs past `end_time`, and the crank reward never dips into rent.
+
+mod common;
+
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::{BurnSchedule, ErrorCode, TickAmount};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    clock::Clock,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+};
+
+const VAULT_BALANCE: u64 = 10_000_000;
+const TICK: u64 = 100_000;
+const INTERVAL: i64 = 3_600;
+const CRANK_REWARD: u64 = 5_000;
+const BOUNTY: u64 = 1_000_000;
+
+struct Schedule {
+    fixture: BurnFixture,
+    vault: Pubkey,
+    address: Pubkey,
+    start_time: i64,
+}
+
+impl Schedule {
+    /// A schedule whose first tick is a minute away, funded with `bounty`.
+    async fn create(
+        context: &mut ProgramTestContext,
+        fixture: BurnFixture,
+        tick_amount: TickAmount,
+        end_after: Option<i64>,
+        bounty: u64,
+    ) -> Self {
+        let vault = fixture.create_vault(context, VAULT_BALANCE).await;
+        let now = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+        let start_time = now + 60;
+        let schedule = Self {
+            address: Pubkey::find_program_address(
+                &[b"burn_schedule", fixture.burn_config.as_ref()],
+                &gigabrain_burn::ID,
+            )
+            .0,
+            fixture,
+            vault,
+            start_time,
+        };
+        let instructions = [
+            schedule.create_instruction(
+                &context.payer.pubkey(),
+                tick_amount,
+                end_after.map(|seconds| start_time + seconds),
+            ),
+            Instruction {
+                program_id: gigabrain_burn::ID,
+                accounts: gigabrain_burn::accounts::FundBurnSchedule {
+                    burn_schedule: schedule.address,
+                    funder: context.payer.pubkey(),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: gigabrain_burn::instruction::FundBurnSchedule { lamports: bounty }.data(),
+            },
+        ];
+        process(context, &instructions, &[]).await.unwrap();
+        schedule
+    }
+
+    fn create_instruction(
+        &self,
+        authority: &Pubkey,
+        tick_amount: TickAmount,
+        end_time: Option<i64>,
+    ) -> Instruction {
+        Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::CreateBurnSchedule {
+                burn_config: self.fixture.burn_config,
+                token_mint: self.fixture.token_mint,
+                burn_schedule: self.address,
+                authority: *authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: gigabrain_burn::instruction::CreateBurnSchedule {
+                tick_amount,
+                interval_seconds: INTERVAL,
+                start_time: self.start_time,
+                end_time,
+                crank_reward: CRANK_REWARD,
+            }
+            .data(),
+        }
+    }
+
+    /// Crank from a fresh, funded cranker, returning it with the result.
+    async fn crank(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> (Keypair, Result<(), BanksClientError>) {
+        let cranker = Keypair::new();
+        let fund =
+            system_instruction::transfer(&context.payer.pubkey(), &cranker.pubkey(), 1_000_000_000);
+        process(context, &[fund], &[]).await.unwrap();
+
+        let instruction = Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::CrankScheduledBurn {
+                burn_config: self.fixture.burn_config,
+                token_mint: self.fixture.token_mint,
+                burn_schedule: self.address,
+                vault: self.vault,
+                global_config: global_config_address().0,
+                cranker: cranker.pubkey(),
+                token_program: self.fixture.token_program,
+            }
+            .to_account_metas(None),
+            data: gigabrain_burn::instruction::CrankScheduledBurn {}.data(),
+        };
+        let result = process(context, &[instruction], &[&cranker]).await;
+        (cranker, result)
+    }
+
+    async fn state(&self, context: &mut ProgramTestContext) -> BurnSchedule {
+        let account = context
+            .banks_client
+            .get_account(self.address)
+            .await
+            .unwrap()
+            .unwrap();
+        BurnSchedule::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+}
+
+async fn setup_schedule(
+    tick_amount: TickAmount,
+    end_after: Option<i64>,
+    bounty: u64,
+) -> (ProgramTestContext, Schedule) {
+    let (mut context, fixture) = setup().await;
+    let schedule = Schedule::create(&mut context, fixture, tick_amount, end_after, bounty).await;
+    (context, schedule)
+}
+
+#[tokio::test]
+async fn cranks_only_due_ticks() {
+    let (mut context, schedule) =
+        setup_schedule(TickAmount::Fixed { amount: TICK }, None, BOUNTY).await;
+
+    let (_, result) = schedule.crank(&mut context).await;
+    assert_program_error(result, ErrorCode::ScheduleTickNotDue);
+
+    advance_clock(&mut context, 60).await;
+    let (cranker, result) = schedule.crank(&mut context).await;
+    result.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &schedule.vault).await,
+        VAULT_BALANCE - TICK
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(cranker.pubkey())
+            .await
+            .unwrap(),
+        1_000_000_000 + CRANK_REWARD
+    );
+    let state = schedule.state(&mut context).await;
+    assert_eq!(state.ticks, 1);
+    assert_eq!(state.next_tick_at, schedule.start_time + INTERVAL);
+
+    // The next tick is an interval away
+    let (_, result) = schedule.crank(&mut context).await;
+    assert_program_error(result, ErrorCode::ScheduleTickNotDue);
+}
+
+#[tokio::test]
+async fn skips_missed_ticks() {
+    let (mut context, schedule) =
+        setup_schedule(TickAmount::Fixed { amount: TICK }, None, BOUNTY).await;
+
+    advance_clock(&mut context, 60 + 3 * INTERVAL + 10).await;
+    let (_, result) = schedule.crank(&mut context).await;
+    result.unwrap();
+
+    // One tick burned, and the schedule resumes at the first tick after now
+    assert_eq!(
+        token_balance(&mut context, &schedule.vault).await,
+        VAULT_BALANCE - TICK
+    );
+    let state = schedule.state(&mut context).await;
+    assert_eq!(state.ticks, 1);
+    assert_eq!(state.next_tick_at, schedule.start_time + 4 * INTERVAL);
+    let (_, result) = schedule.crank(&mut context).await;
+    assert_program_error(result, ErrorCode::ScheduleTickNotDue);
+}
+
+#[tokio::test]
+async fn stops_at_end_time() {
+    let (mut context, schedule) = setup_schedule(
+        TickAmount::Fixed { amount: TICK },
+        Some(2 * INTERVAL),
+        BOUNTY,
+    )
+    .await;
+
+    advance_clock(&mut context, 60).await;
+    schedule.crank(&mut context).await.1.unwrap();
+    advance_clock(&mut context, INTERVAL).await;
+    schedule.crank(&mut context).await.1.unwrap();
+
+    // The third tick would fall on end_time
+    advance_clock(&mut context, INTERVAL).await;
+    let (_, result) = schedule.crank(&mut context).await;
+    assert_program_error(result, ErrorCode::ScheduleEnded);
+    assert_eq!(
+        token_balance(&mut context, &schedule.vault).await,
+        VAULT_BALANCE - 2 * TICK
+    );
+}
+
+#[tokio::test]
+async fn clamps_reward_to_bounty_above_rent() {
+    let (mut context, schedule) =
+        setup_schedule(TickAmount::Fixed { amount: TICK }, None, CRANK_REWARD / 2).await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let schedule_account = context
+        .banks_client
+        .get_account(schedule.address)
+        .await
+        .unwrap()
+        .unwrap();
+    let rent_exempt = rent.minimum_balance(schedule_account.data.len());
+
+    advance_clock(&mut context, 60).await;
+    let (cranker, result) = schedule.crank(&mut context).await;
+    result.unwrap();
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(cranker.pubkey())
+            .await
+            .unwrap(),
+        1_000_000_000 + CRANK_REWARD / 2
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(schedule.address)
+            .await
+            .unwrap(),
+        rent_exempt
+    );
+
+    // An empty bounty still lets the burn through, unpaid
+    advance_clock(&mut context, INTERVAL).await;
+    let (cranker, result) = schedule.crank(&mut context).await;
+    result.unwrap();
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(cranker.pubkey())
+            .await
+            .unwrap(),
+        1_000_000_000
+    );
+}
+
+#[tokio::test]
+async fn rejects_ticks_that_need_the_guardian_quorum() {
+    let (mut context, fixture) = setup().await;
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::AddGuardian {
+                guardian: Pubkey::new_unique(),
+            },
+        )
+        .await
+        .unwrap();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetGuardianQuorum {
+                guardian_quorum: 1,
+                quorum_burn_threshold: TICK,
+            },
+        )
+        .await
+        .unwrap();
+
+    // 1% of the vault is exactly the threshold at tick time
+    let schedule = Schedule::create(
+        &mut context,
+        fixture,
+        TickAmount::BalanceBps { bps: 100 },
+        None,
+        BOUNTY,
+    )
+    .await;
+    advance_clock(&mut context, 60).await;
+    let (_, result) = schedule.crank(&mut context).await;
+    assert_program_error(result, ErrorCode::ScheduledBurnNeedsQuorum);
+    assert_eq!(
+        token_balance(&mut context, &schedule.vault).await,
+        VAULT_BALANCE
+    );
+
+    // A fixed tick that large is refused up front
+    let authority = context.payer.pubkey();
+    let close = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::CloseBurnSchedule {
+            burn_config: schedule.fixture.burn_config,
+            token_mint: schedule.fixture.token_mint,
+            burn_schedule: schedule.address,
+            authority,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::CloseBurnSchedule {}.data(),
+    };
+    process(&mut context, &[close], &[]).await.unwrap();
+    let create = schedule.create_instruction(&authority, TickAmount::Fixed { amount: TICK }, None);
+    let result = process(&mut context, &[create], &[]).await;
+    assert_program_error(result, ErrorCode::ScheduledBurnNeedsQuorum);
+}
@@ -0,0 +1,139 @@
+//! Burn vault: tokens leave the program-owned vault only through burns by the
+//! config's own authority or operators, signed for by the config PDA.
+
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::{BurnConfig, BurnMode, ErrorCode};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+const VAULT_BALANCE: u64 = 10 * AMOUNT;
+
+#[tokio::test]
+async fn burns_from_vault() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let vault = fixture.create_vault(&mut context, VAULT_BALANCE).await;
+    let supply_before = mint_supply(&mut context, &fixture.token_mint).await;
+
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        fixture.payment_instruction(&authority, PAYMENT_AMOUNT),
+        fixture.execute_instruction(
+            &authority,
+            &authority,
+            None,
+            &vault,
+            BurnMode::Amount { amount: AMOUNT },
+            "x402-vault",
+        ),
+    ];
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    assert_eq!(
+        token_balance(&mut context, &vault).await,
+        VAULT_BALANCE - AMOUNT
+    );
+    assert_eq!(
+        mint_supply(&mut context, &fixture.token_mint).await,
+        supply_before - AMOUNT
+    );
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE - VAULT_BALANCE
+    );
+}
+
+#[tokio::test]
+async fn rejects_vault_burn_by_stranger() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let vault = fixture.create_vault(&mut context, VAULT_BALANCE).await;
+    let stranger = Keypair::new();
+
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        fixture.payment_instruction(&authority, PAYMENT_AMOUNT),
+        fixture.execute_instruction(
+            &stranger.pubkey(),
+            &authority,
+            None,
+            &vault,
+            BurnMode::Amount { amount: AMOUNT },
+            "x402-stranger",
+        ),
+    ];
+    let result = process(&mut context, &instructions, &[&stranger]).await;
+
+    assert_program_error(result, ErrorCode::UnauthorizedExecutor);
+    assert_eq!(token_balance(&mut context, &vault).await, VAULT_BALANCE);
+}
+
+#[tokio::test]
+async fn rejects_vault_burn_through_another_config() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let vault = fixture.create_vault(&mut context, VAULT_BALANCE).await;
+
+    // An attacker's own config on the same mint, with its own oracle
+    let attacker = Keypair::new();
+    let attacker_oracle = Keypair::new();
+    let (attacker_config, _) = BurnConfig::pda(&attacker.pubkey(), &fixture.token_mint, CONFIG_ID);
+    let instructions = [
+        system_instruction::transfer(&authority, &attacker.pubkey(), 1_000_000_000),
+        Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::InitializeBurnConfig {
+                burn_config: attacker_config,
+                token_mint: fixture.token_mint,
+                payment_mint: fixture.payment_mint,
+                payment_treasury: fixture.payment_treasury,
+                authority: attacker.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: gigabrain_burn::instruction::InitializeBurnConfig {
+                config_id: CONFIG_ID,
+                profit_threshold: PROFIT_THRESHOLD,
+                burn_percentage: BURN_PERCENTAGE,
+                min_burn_amount: MIN_BURN_AMOUNT,
+                payment_amount: PAYMENT_AMOUNT,
+                profit_oracle: attacker_oracle.pubkey(),
+            }
+            .data(),
+        },
+    ];
+    process(&mut context, &instructions, &[&attacker])
+        .await
+        .unwrap();
+    let attacker_fixture = BurnFixture {
+        burn_config: attacker_config,
+        profit_oracle: attacker_oracle,
+        token_account: vault,
+        ..fixture
+    };
+
+    let instructions = [
+        attacker_fixture.profit_attestation(&attacker_fixture.profit_report(PROFIT_AMOUNT, 1)),
+        attacker_fixture.payment_instruction(&authority, PAYMENT_AMOUNT),
+        attacker_fixture.execute_instruction(
+            &attacker.pubkey(),
+            &authority,
+            None,
+            &vault,
+            BurnMode::Amount { amount: AMOUNT },
+            "x402-foreign-vault",
+        ),
+    ];
+    let result = process(&mut context, &instructions, &[&attacker]).await;
+
+    assert_program_error(result, ErrorCode::InvalidBurnSource);
+    assert_eq!(token_balance(&mut context, &vault).await, VAULT_BALANCE);
+}
@@ -0,0 +1,400 @@
+//! Buyback-and-burn through the mock swap program: the swap output lands in
+//! the executor's token account and is burned in the same instruction, for
+//! the same x402 payment and protocol fee as any other burn.
+
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::{ErrorCode, Sentiment};
+use solana_program_test::{processor, ProgramTestContext};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program, sysvar,
+};
+
+const POOL_BALANCE: u64 = 50_000_000;
+const LAMPORTS_IN: u64 = 1_000_000_000;
+const AMOUNT_OUT: u64 = 2_000_000;
+const MIN_AMOUNT_OUT: u64 = 1_900_000;
+const FEE_BPS: u16 = 100;
+
+struct Buyback {
+    fixture: BurnFixture,
+    pool_token_account: Pubkey,
+}
+
+impl Buyback {
+    async fn setup(context: &mut ProgramTestContext) -> Self {
+        let fixture = BurnFixture::setup(context, &spl_token::ID, &[]).await;
+        let pool_token_account = create_token_account(
+            context,
+            &spl_token::ID,
+            &fixture.token_mint,
+            &[],
+            &mock_swap::pool_authority().0,
+            POOL_BALANCE,
+        )
+        .await;
+
+        let buyback = Self {
+            fixture,
+            pool_token_account,
+        };
+        buyback
+            .update_allowlist(
+                context,
+                gigabrain_burn::instruction::AddSwapProgram {
+                    swap_program: mock_swap::ID,
+                }
+                .data(),
+            )
+            .await;
+        buyback
+    }
+
+    async fn update_allowlist(&self, context: &mut ProgramTestContext, data: Vec<u8>) {
+        let instruction = Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::UpdateBurnConfig {
+                burn_config: self.fixture.burn_config,
+                token_mint: self.fixture.token_mint,
+                authority: context.payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data,
+        };
+        process(context, &[instruction], &[]).await.unwrap();
+    }
+
+    /// The x402 payment and `buyback_and_burn` routing a mock swap of
+    /// `LAMPORTS_IN` for `amount_out`
+    fn instructions(
+        &self,
+        executor: &Pubkey,
+        amount_out: u64,
+        min_amount_out: u64,
+        x402_signature: &str,
+    ) -> Vec<Instruction> {
+        let accounts = self.accounts(executor, x402_signature);
+        vec![
+            self.fixture.payment_instruction(executor, PAYMENT_AMOUNT),
+            self.instruction(
+                accounts,
+                executor,
+                amount_out,
+                min_amount_out,
+                x402_signature,
+            ),
+        ]
+    }
+
+    /// Accounts for a `VerifiedTransfer` buyback by `executor`, who also pays
+    fn accounts(
+        &self,
+        executor: &Pubkey,
+        x402_signature: &str,
+    ) -> gigabrain_burn::accounts::BuybackAndBurn {
+        gigabrain_burn::accounts::BuybackAndBurn {
+            burn_config: self.fixture.burn_config,
+            token_mint: self.fixture.token_mint,
+            token_account: self.fixture.token_account,
+            executor: *executor,
+            burn_operator: None,
+            global_config: global_config_address().0,
+            swap_program: mock_swap::ID,
+            payer: *executor,
+            payment_receipt: Some(payment_receipt_address(x402_signature)),
+            instructions: sysvar::instructions::ID,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+            payment_source: None,
+            payment_mint: None,
+            payment_treasury: None,
+            payment_token_program: None,
+            credit_account: None,
+            fee_schedule: fee_schedule_address().0,
+            fee_exemption: None,
+            fee_token_account: None,
+        }
+    }
+
+    /// `buyback_and_burn` over `accounts`
+    fn instruction(
+        &self,
+        accounts: gigabrain_burn::accounts::BuybackAndBurn,
+        executor: &Pubkey,
+        amount_out: u64,
+        min_amount_out: u64,
+        x402_signature: &str,
+    ) -> Instruction {
+        let swap = mock_swap::swap(
+            executor,
+            &self.pool_token_account,
+            &self.fixture.token_account,
+            &self.fixture.token_mint,
+            &spl_token::ID,
+            LAMPORTS_IN,
+            amount_out,
+        );
+
+        let mut accounts = accounts.to_account_metas(None);
+        accounts.extend(swap.accounts);
+
+        Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts,
+            data: gigabrain_burn::instruction::BuybackAndBurn {
+                min_amount_out,
+                swap_data: swap.data,
+                x402_signature: x402_signature.to_string(),
+            }
+            .data(),
+        }
+    }
+}
+
+fn buyback_program_test() -> solana_program_test::ProgramTest {
+    let mut program_test = program_test();
+    program_test.add_program(
+        "mock_swap",
+        mock_swap::ID,
+        processor!(mock_swap::process_instruction),
+    );
+    program_test
+}
+
+#[tokio::test]
+async fn burns_exactly_the_swap_output() {
+    let mut context = buyback_program_test().start_with_context().await;
+    let buyback = Buyback::setup(&mut context).await;
+    let executor = context.payer.pubkey();
+
+    let instructions = buyback.instructions(&executor, AMOUNT_OUT, MIN_AMOUNT_OUT, "x402-buyback");
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    let fixture = &buyback.fixture;
+    assert_eq!(
+        mint_supply(&mut context, &fixture.token_mint).await,
+        INITIAL_BALANCE + POOL_BALANCE - AMOUNT_OUT
+    );
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE
+    );
+    assert_eq!(
+        token_balance(&mut context, &buyback.pool_token_account).await,
+        POOL_BALANCE - AMOUNT_OUT
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(mock_swap::pool_authority().0)
+            .await
+            .unwrap(),
+        LAMPORTS_IN
+    );
+    assert_eq!(
+        token_balance(&mut context, &fixture.payment_treasury).await,
+        PAYMENT_AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn rejects_swap_below_min_amount_out() {
+    let mut context = buyback_program_test().start_with_context().await;
+    let buyback = Buyback::setup(&mut context).await;
+    let executor = context.payer.pubkey();
+
+    let instructions = buyback.instructions(
+        &executor,
+        MIN_AMOUNT_OUT - 1,
+        MIN_AMOUNT_OUT,
+        "x402-slippage",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::SlippageExceeded);
+}
+
+#[tokio::test]
+async fn rejects_swap_program_off_the_allowlist() {
+    let mut context = buyback_program_test().start_with_context().await;
+    let buyback = Buyback::setup(&mut context).await;
+    let executor = context.payer.pubkey();
+    buyback
+        .update_allowlist(
+            &mut context,
+            gigabrain_burn::instruction::RemoveSwapProgram {
+                swap_program: mock_swap::ID,
+            }
+            .data(),
+        )
+        .await;
+
+    let instructions =
+        buyback.instructions(&executor, AMOUNT_OUT, MIN_AMOUNT_OUT, "x402-off-allowlist");
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::SwapProgramNotAllowed);
+}
+
+#[tokio::test]
+async fn rejects_zero_min_amount_out() {
+    let mut context = buyback_program_test().start_with_context().await;
+    let buyback = Buyback::setup(&mut context).await;
+    let executor = context.payer.pubkey();
+
+    let instructions = buyback.instructions(&executor, AMOUNT_OUT, 0, "x402-zero-min");
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::InvalidMinAmountOut);
+}
+
+#[tokio::test]
+async fn large_buyback_needs_guardian_quorum() {
+    let mut context = buyback_program_test().start_with_context().await;
+    let buyback = Buyback::setup(&mut context).await;
+    let executor = context.payer.pubkey();
+    let guardian = Keypair::new();
+    buyback
+        .fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::AddGuardian {
+                guardian: guardian.pubkey(),
+            },
+        )
+        .await
+        .unwrap();
+    // Quorum is judged on what the swap delivers, not on min_amount_out
+    buyback
+        .fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetGuardianQuorum {
+                guardian_quorum: 1,
+                quorum_burn_threshold: AMOUNT_OUT,
+            },
+        )
+        .await
+        .unwrap();
+
+    let mut instructions =
+        buyback.instructions(&executor, AMOUNT_OUT, MIN_AMOUNT_OUT, "x402-quorum");
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::QuorumNotMet);
+
+    instructions[1]
+        .accounts
+        .push(AccountMeta::new_readonly(guardian.pubkey(), true));
+    process(&mut context, &instructions, &[&guardian])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn buyback_needs_ai_decision_when_configured() {
+    let mut context = buyback_program_test().start_with_context().await;
+    let buyback = Buyback::setup(&mut context).await;
+    let executor = context.payer.pubkey();
+    let ai_signer = Keypair::new();
+    buyback
+        .fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetAiPolicy {
+                ai_signer: Some(ai_signer.pubkey()),
+                min_ai_confidence: 0,
+                require_positive_sentiment: false,
+            },
+        )
+        .await
+        .unwrap();
+
+    let mut instructions = buyback.instructions(&executor, AMOUNT_OUT, MIN_AMOUNT_OUT, "x402-ai");
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::AiDecisionMissing);
+
+    // A decision sized for the minimum does not cover what the swap delivers
+    let undersized =
+        buyback
+            .fixture
+            .ai_attestation(&ai_signer, 0, MIN_AMOUNT_OUT, 90, Sentiment::Neutral);
+    instructions.insert(0, undersized);
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::AiDecisionAmountExceeded);
+
+    instructions[0] =
+        buyback
+            .fixture
+            .ai_attestation(&ai_signer, 0, AMOUNT_OUT, 90, Sentiment::Neutral);
+    process(&mut context, &instructions, &[]).await.unwrap();
+    assert_eq!(
+        buyback.fixture.config(&mut context).await.ai_decision_nonce,
+        1
+    );
+}
+
+#[tokio::test]
+async fn rejects_buyback_without_payment() {
+    let mut context = buyback_program_test().start_with_context().await;
+    let buyback = Buyback::setup(&mut context).await;
+    let executor = context.payer.pubkey();
+
+    let accounts = buyback.accounts(&executor, "x402-unpaid");
+    let instruction = buyback.instruction(
+        accounts,
+        &executor,
+        AMOUNT_OUT,
+        MIN_AMOUNT_OUT,
+        "x402-unpaid",
+    );
+    let result = process(&mut context, &[instruction], &[]).await;
+
+    assert_program_error(result, ErrorCode::PaymentNotFound);
+}
+
+#[tokio::test]
+async fn deducts_protocol_fee_from_swap_output() {
+    let mut context = buyback_program_test().start_with_context().await;
+    let buyback = Buyback::setup(&mut context).await;
+    let executor = context.payer.pubkey();
+    let fixture = &buyback.fixture;
+    set_upgrade_authority(&mut context, Some(executor));
+    let fee_destination = Pubkey::new_unique();
+    let instruction = initialize_fee_schedule_instruction(&executor, FEE_BPS, 0, &fee_destination);
+    process(&mut context, &[instruction], &[]).await.unwrap();
+    let fee_token_account = create_token_account(
+        &mut context,
+        &spl_token::ID,
+        &fixture.token_mint,
+        &[],
+        &fee_destination,
+        0,
+    )
+    .await;
+    let fee = AMOUNT_OUT * FEE_BPS as u64 / 10_000;
+
+    let accounts = gigabrain_burn::accounts::BuybackAndBurn {
+        fee_token_account: Some(fee_token_account),
+        ..buyback.accounts(&executor, "x402-fee")
+    };
+    let instructions = [
+        fixture.payment_instruction(&executor, PAYMENT_AMOUNT),
+        buyback.instruction(accounts, &executor, AMOUNT_OUT, MIN_AMOUNT_OUT, "x402-fee"),
+    ];
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    assert_eq!(token_balance(&mut context, &fee_token_account).await, fee);
+    assert_eq!(
+        fixture.config(&mut context).await.total_burned,
+        AMOUNT_OUT - fee
+    );
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE
+    );
+}
@@ -0,0 +1,879 @@
+//! Shared fixtures for the gigabrain-burn program tests.
+//!
+//! The programs run natively inside program-test (see [`program_test`]), so
+//! `cargo test` needs no SBF build.
+
+#![allow(dead_code)]
+
+use anchor_lang::{AccountDeserialize, AnchorSerialize, InstructionData, ToAccountMetas};
+use gigabrain_burn::{
+    AiDecision, BurnConfig, BurnMode, ErrorCode, ProfitReport, PythPrice, Sentiment,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{from_account, Account, AccountSharedData},
+    account_info::AccountInfo,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::Clock,
+    commitment_config::CommitmentLevel,
+    ed25519_program,
+    entrypoint::ProgramResult,
+    hash::hash,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use spl_token_2022::extension::{transfer_fee, ExtensionType, StateWithExtensions};
+
+pub const DECIMALS: u8 = 6;
+pub const INITIAL_BALANCE: u64 = 100_000_000;
+pub const PROFIT_THRESHOLD: u64 = 1_000_000;
+pub const BURN_PERCENTAGE: u16 = 2500;
+pub const MIN_BURN_AMOUNT: u64 = 1;
+pub const PAYMENT_AMOUNT: u64 = 5_000;
+pub const CONFIG_ID: u64 = 0;
+
+pub fn program_test() -> ProgramTest {
+    ProgramTest::new(
+        "gigabrain_burn",
+        gigabrain_burn::ID,
+        processor!(process_instruction),
+    )
+}
+
+/// Native entrypoint for program-test.
+///
+/// Anchor's `entry` ties the account slice to the accounts' own lifetime,
+/// which program-test's processor signature cannot express, so the slice is
+/// copied and leaked for the duration of the test.
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(accounts.to_vec().into_boxed_slice());
+    gigabrain_burn::entry(program_id, accounts, data)
+}
+
+/// A started test validator with an SPL Token burn config owned by the payer.
+pub async fn setup() -> (ProgramTestContext, BurnFixture) {
+    let mut context = program_test().start_with_context().await;
+    let fixture = BurnFixture::setup(&mut context, &spl_token::ID, &[]).await;
+    (context, fixture)
+}
+
+pub fn global_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"global_config"], &gigabrain_burn::ID)
+}
+
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[gigabrain_burn::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+/// Give the natively loaded program the ProgramData account the upgradeable
+/// loader would have created, naming `upgrade_authority`.
+pub fn set_upgrade_authority(context: &mut ProgramTestContext, upgrade_authority: Option<Pubkey>) {
+    let state = UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: upgrade_authority,
+    };
+    let account = Account::new_data(1_000_000_000, &state, &bpf_loader_upgradeable::ID).unwrap();
+    context.set_account(&program_data_address(), &AccountSharedData::from(account));
+}
+
+pub fn initialize_global_config_instruction(admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::InitializeGlobalConfig {
+            global_config: global_config_address().0,
+            program_data: program_data_address(),
+            admin: *admin,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::InitializeGlobalConfig {}.data(),
+    }
+}
+
+pub fn set_global_paused_instruction(admin: &Pubkey, paused: bool) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::SetGlobalPaused {
+            global_config: global_config_address().0,
+            program_data: program_data_address(),
+            admin: *admin,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::SetGlobalPaused { paused }.data(),
+    }
+}
+
+/// `initialize_fee_schedule` signed by `admin`.
+pub fn initialize_fee_schedule_instruction(
+    admin: &Pubkey,
+    fee_bps: u16,
+    free_burns: u64,
+    fee_destination: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::InitializeFeeSchedule {
+            fee_schedule: fee_schedule_address().0,
+            program_data: program_data_address(),
+            admin: *admin,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::InitializeFeeSchedule {
+            fee_bps,
+            free_burns,
+            fee_destination: *fee_destination,
+        }
+        .data(),
+    }
+}
+
+pub fn fee_schedule_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_schedule"], &gigabrain_burn::ID)
+}
+
+pub async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    // Sign with the context's blockhash so `get_new_latest_blockhash` reliably
+    // separates otherwise identical transactions; banks would answer a
+    // duplicate with the first one's status instead of running it
+    let blockhash = context.last_blockhash;
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Assert that a transaction failed with the program's `expected` error.
+pub fn assert_program_error(result: Result<(), BanksClientError>, expected: ErrorCode) {
+    assert_error_code(result, expected);
+}
+
+/// Assert that a transaction failed with one of Anchor's own `expected` errors.
+pub fn assert_anchor_error(
+    result: Result<(), BanksClientError>,
+    expected: anchor_lang::error::ErrorCode,
+) {
+    assert_error_code(result, expected);
+}
+
+/// Assert that a transaction failed with the token program's `expected` error.
+pub fn assert_token_error(
+    result: Result<(), BanksClientError>,
+    expected: spl_token::error::TokenError,
+) {
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, expected.clone() as u32, "expected {expected:?}"),
+        other => panic!("expected {expected:?}, got {other:?}"),
+    }
+}
+
+fn assert_error_code<E: Into<u32> + Copy + std::fmt::Debug>(
+    result: Result<(), BanksClientError>,
+    expected: E,
+) {
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, expected.into(), "expected {expected:?}"),
+        other => panic!("expected {expected:?}, got {other:?}"),
+    }
+}
+
+/// A mock Pyth legacy price account in the layout `PythPrice` reads.
+pub fn pyth_price_account(price: i64, conf: u64, expo: i32, publish_time: i64) -> Account {
+    let mut data = vec![0u8; 3312];
+    let mut write =
+        |offset: usize, bytes: &[u8]| data[offset..offset + bytes.len()].copy_from_slice(bytes);
+    write(PythPrice::MAGIC_OFFSET, &PythPrice::MAGIC.to_le_bytes());
+    write(
+        PythPrice::ACCOUNT_TYPE_OFFSET,
+        &PythPrice::ACCOUNT_TYPE_PRICE.to_le_bytes(),
+    );
+    write(PythPrice::EXPO_OFFSET, &expo.to_le_bytes());
+    write(PythPrice::TIMESTAMP_OFFSET, &publish_time.to_le_bytes());
+    write(PythPrice::AGG_PRICE_OFFSET, &price.to_le_bytes());
+    write(PythPrice::AGG_CONF_OFFSET, &conf.to_le_bytes());
+    write(
+        PythPrice::AGG_STATUS_OFFSET,
+        &PythPrice::STATUS_TRADING.to_le_bytes(),
+    );
+
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: Pubkey::new_unique(),
+        ..Account::default()
+    }
+}
+
+/// Create a mint owned by `token_program` with the given mint extensions.
+pub async fn create_mint(
+    context: &mut ProgramTestContext,
+    token_program: &Pubkey,
+    extensions: &[ExtensionType],
+) -> Pubkey {
+    let mint = Keypair::new();
+    let authority = context.payer.pubkey();
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions)
+        .unwrap();
+    let lamports = context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(space);
+
+    let mut instructions = vec![system_instruction::create_account(
+        &authority,
+        &mint.pubkey(),
+        lamports,
+        space as u64,
+        token_program,
+    )];
+    for extension in extensions {
+        match extension {
+            ExtensionType::TransferFeeConfig => instructions.push(
+                transfer_fee::instruction::initialize_transfer_fee_config(
+                    token_program,
+                    &mint.pubkey(),
+                    Some(&authority),
+                    Some(&authority),
+                    100,
+                    1_000_000,
+                )
+                .unwrap(),
+            ),
+            other => panic!("unsupported test mint extension: {other:?}"),
+        }
+    }
+    instructions.push(
+        spl_token_2022::instruction::initialize_mint2(
+            token_program,
+            &mint.pubkey(),
+            &authority,
+            None,
+            DECIMALS,
+        )
+        .unwrap(),
+    );
+
+    process(context, &instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// Create a token account for `mint` and mint `amount` into it.
+pub async fn create_token_account(
+    context: &mut ProgramTestContext,
+    token_program: &Pubkey,
+    mint: &Pubkey,
+    mint_extensions: &[ExtensionType],
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let account = Keypair::new();
+    let payer = context.payer.pubkey();
+    let extensions = ExtensionType::get_required_init_account_extensions(mint_extensions);
+    let space =
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&extensions)
+            .unwrap();
+    let lamports = context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(space);
+
+    let mut instructions = vec![
+        system_instruction::create_account(
+            &payer,
+            &account.pubkey(),
+            lamports,
+            space as u64,
+            token_program,
+        ),
+        spl_token_2022::instruction::initialize_account3(
+            token_program,
+            &account.pubkey(),
+            mint,
+            owner,
+        )
+        .unwrap(),
+    ];
+    if amount > 0 {
+        instructions.push(
+            spl_token_2022::instruction::mint_to(
+                token_program,
+                mint,
+                &account.pubkey(),
+                &payer,
+                &[],
+                amount,
+            )
+            .unwrap(),
+        );
+    }
+
+    process(context, &instructions, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+/// Move the cluster clock `seconds` forward (slots are left alone).
+pub async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+    // Read the working bank's clock: the default commitment can lag behind a
+    // previous advance and would set the clock back
+    let account = context
+        .banks_client
+        .get_account_with_commitment(sysvar::clock::ID, CommitmentLevel::Processed)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut clock: Clock = from_account(&account).unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+pub async fn mint_supply(context: &mut ProgramTestContext, mint: &Pubkey) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(*mint)
+        .await
+        .unwrap()
+        .unwrap();
+    StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data)
+        .unwrap()
+        .base
+        .supply
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, token_account: &Pubkey) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(*token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+        .unwrap()
+        .base
+        .amount
+}
+
+/// Build an Ed25519 program instruction verifying `signer`'s signature over
+/// `message`, with the key, signature and message all inline.
+pub fn ed25519_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+    let pubkey_offset = OFFSETS_START + OFFSETS_SIZE;
+    let signature_offset = pubkey_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let signature = signer.sign_message(message);
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.extend_from_slice(&[1, 0]);
+    for value in [
+        signature_offset as u16,
+        u16::MAX,
+        pubkey_offset as u16,
+        u16::MAX,
+        message_offset as u16,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+pub fn burn_operator_address(burn_config: &Pubkey, operator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"burn_operator", burn_config.as_ref(), operator.as_ref()],
+        &gigabrain_burn::ID,
+    )
+    .0
+}
+
+/// Address of the receipt a burn paid with `x402_signature` creates.
+pub fn payment_receipt_address(x402_signature: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"payment_receipt",
+            &hash(x402_signature.as_bytes()).to_bytes(),
+        ],
+        &gigabrain_burn::ID,
+    )
+    .0
+}
+
+/// `execute_autonomous_burn` over `accounts`.
+pub fn execute_burn_instruction(
+    accounts: gigabrain_burn::accounts::ExecuteAutonomousBurn,
+    mode: BurnMode,
+    x402_signature: &str,
+) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: accounts.to_account_metas(None),
+        data: gigabrain_burn::instruction::ExecuteAutonomousBurn {
+            mode,
+            x402_signature: x402_signature.to_string(),
+        }
+        .data(),
+    }
+}
+
+/// `burn_all_and_close` over `accounts`.
+pub fn close_instruction(
+    accounts: gigabrain_burn::accounts::BurnAllAndClose,
+    x402_signature: &str,
+) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: accounts.to_account_metas(None),
+        data: gigabrain_burn::instruction::BurnAllAndClose {
+            x402_signature: x402_signature.to_string(),
+        }
+        .data(),
+    }
+}
+
+/// A burn config with its token, payment and oracle accounts.
+///
+/// The test context payer acts as config authority, executor and x402 payer.
+pub struct BurnFixture {
+    pub token_program: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub payment_mint: Pubkey,
+    pub payment_source: Pubkey,
+    pub payment_treasury: Pubkey,
+    pub burn_config: Pubkey,
+    pub profit_oracle: Keypair,
+    pub price_feed: Option<Pubkey>,
+}
+
+impl BurnFixture {
+    pub async fn setup(
+        context: &mut ProgramTestContext,
+        token_program: &Pubkey,
+        mint_extensions: &[ExtensionType],
+    ) -> Self {
+        let authority = context.payer.pubkey();
+        let profit_oracle = Keypair::new();
+
+        let token_mint = create_mint(context, token_program, mint_extensions).await;
+        let token_account = create_token_account(
+            context,
+            token_program,
+            &token_mint,
+            mint_extensions,
+            &authority,
+            INITIAL_BALANCE,
+        )
+        .await;
+
+        let payment_mint = create_mint(context, &spl_token::ID, &[]).await;
+        let payment_source = create_token_account(
+            context,
+            &spl_token::ID,
+            &payment_mint,
+            &[],
+            &authority,
+            INITIAL_BALANCE,
+        )
+        .await;
+        let payment_treasury = create_token_account(
+            context,
+            &spl_token::ID,
+            &payment_mint,
+            &[],
+            &Pubkey::new_unique(),
+            0,
+        )
+        .await;
+
+        let (burn_config, _) = BurnConfig::pda(&authority, &token_mint, CONFIG_ID);
+
+        let initialize = Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::InitializeBurnConfig {
+                burn_config,
+                token_mint,
+                payment_mint,
+                payment_treasury,
+                authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: gigabrain_burn::instruction::InitializeBurnConfig {
+                config_id: CONFIG_ID,
+                profit_threshold: PROFIT_THRESHOLD,
+                burn_percentage: BURN_PERCENTAGE,
+                min_burn_amount: MIN_BURN_AMOUNT,
+                payment_amount: PAYMENT_AMOUNT,
+                profit_oracle: profit_oracle.pubkey(),
+            }
+            .data(),
+        };
+        process(context, &[initialize], &[]).await.unwrap();
+
+        Self {
+            token_program: *token_program,
+            token_mint,
+            token_account,
+            payment_mint,
+            payment_source,
+            payment_treasury,
+            burn_config,
+            profit_oracle,
+            price_feed: None,
+        }
+    }
+
+    /// Send an authority-only `UpdateBurnConfig` instruction such as
+    /// `set_burn_limits` or `add_guardian`.
+    pub async fn update(
+        &self,
+        context: &mut ProgramTestContext,
+        data: impl InstructionData,
+    ) -> Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::UpdateBurnConfig {
+                burn_config: self.burn_config,
+                token_mint: self.token_mint,
+                authority: context.payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: data.data(),
+        };
+        process(context, &[instruction], &[]).await
+    }
+
+    /// Create the config's burn vault and move `amount` tokens into it from
+    /// the fixture's token account.
+    pub async fn create_vault(&self, context: &mut ProgramTestContext, amount: u64) -> Pubkey {
+        let authority = context.payer.pubkey();
+        let (vault, _) = Pubkey::find_program_address(
+            &[b"burn_vault", self.burn_config.as_ref()],
+            &gigabrain_burn::ID,
+        );
+        let instructions = [
+            Instruction {
+                program_id: gigabrain_burn::ID,
+                accounts: gigabrain_burn::accounts::InitializeBurnVault {
+                    burn_config: self.burn_config,
+                    token_mint: self.token_mint,
+                    vault,
+                    authority,
+                    token_program: self.token_program,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: gigabrain_burn::instruction::InitializeBurnVault {}.data(),
+            },
+            spl_token_2022::instruction::transfer_checked(
+                &self.token_program,
+                &self.token_account,
+                &self.token_mint,
+                &vault,
+                &authority,
+                &[],
+                amount,
+                DECIMALS,
+            )
+            .unwrap(),
+        ];
+        process(context, &instructions, &[]).await.unwrap();
+        vault
+    }
+
+    /// Price profits through `price_feed`, quoted with `quote_decimals`.
+    pub async fn set_price_feed(
+        &mut self,
+        context: &mut ProgramTestContext,
+        price_feed: Pubkey,
+        quote_decimals: u8,
+        max_price_age: i64,
+        max_price_confidence_bps: u16,
+    ) {
+        self.update(
+            context,
+            gigabrain_burn::instruction::SetPriceFeed {
+                price_feed: Some(price_feed),
+                quote_decimals,
+                max_price_age,
+                max_price_confidence_bps,
+            },
+        )
+        .await
+        .unwrap();
+        self.price_feed = Some(price_feed);
+    }
+
+    /// The burn config's current state.
+    pub async fn config(&self, context: &mut ProgramTestContext) -> BurnConfig {
+        let account = context
+            .banks_client
+            .get_account(self.burn_config)
+            .await
+            .unwrap()
+            .unwrap();
+        BurnConfig::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// A never-expiring profit report for this config.
+    pub fn profit_report(&self, profit_amount: u64, period_id: u64) -> ProfitReport {
+        ProfitReport {
+            burn_config: self.burn_config,
+            profit_amount,
+            period_id,
+            expiry_slot: u64::MAX,
+        }
+    }
+
+    /// Ed25519 instruction in which the profit oracle signs `report`.
+    pub fn profit_attestation(&self, report: &ProfitReport) -> Instruction {
+        ed25519_instruction(&self.profit_oracle, &report.try_to_vec().unwrap())
+    }
+
+    /// Ed25519 instruction in which `ai_signer` approves the burn carrying
+    /// `nonce`, of up to `max_amount`, never expiring.
+    pub fn ai_attestation(
+        &self,
+        ai_signer: &Keypair,
+        nonce: u64,
+        max_amount: u64,
+        confidence: u8,
+        sentiment: Sentiment,
+    ) -> Instruction {
+        let decision = AiDecision {
+            burn_config: self.burn_config,
+            nonce,
+            max_amount,
+            approved: true,
+            confidence,
+            sentiment,
+            reasoning_hash: [7; 32],
+            expiry_slot: u64::MAX,
+        };
+        ed25519_instruction(ai_signer, &decision.try_to_vec().unwrap())
+    }
+
+    /// x402 `transfer_checked` of `amount` from the payment source to the
+    /// treasury, signed by `payer`.
+    pub fn payment_instruction(&self, payer: &Pubkey, amount: u64) -> Instruction {
+        spl_token_2022::instruction::transfer_checked(
+            &spl_token::ID,
+            &self.payment_source,
+            &self.payment_mint,
+            &self.payment_treasury,
+            payer,
+            &[],
+            amount,
+            DECIMALS,
+        )
+        .unwrap()
+    }
+
+    /// `execute_autonomous_burn` from the fixture's token account, with
+    /// `executor` also paying for the receipt.
+    pub fn burn_instruction(
+        &self,
+        executor: &Pubkey,
+        mode: BurnMode,
+        x402_signature: &str,
+    ) -> Instruction {
+        self.execute_instruction(
+            executor,
+            executor,
+            None,
+            &self.token_account,
+            mode,
+            x402_signature,
+        )
+    }
+
+    /// `execute_autonomous_burn` of `amount` by the registered `operator` from
+    /// `token_account`, with `payer` making the x402 payment.
+    pub fn operator_burn_instruction(
+        &self,
+        operator: &Pubkey,
+        payer: &Pubkey,
+        token_account: &Pubkey,
+        amount: u64,
+        x402_signature: &str,
+    ) -> Instruction {
+        self.execute_instruction(
+            operator,
+            payer,
+            Some(burn_operator_address(&self.burn_config, operator)),
+            token_account,
+            BurnMode::Amount { amount },
+            x402_signature,
+        )
+    }
+
+    /// `execute_autonomous_burn` from any `token_account`, e.g. the burn vault.
+    pub fn execute_instruction(
+        &self,
+        executor: &Pubkey,
+        payer: &Pubkey,
+        burn_operator: Option<Pubkey>,
+        token_account: &Pubkey,
+        mode: BurnMode,
+        x402_signature: &str,
+    ) -> Instruction {
+        let accounts = gigabrain_burn::accounts::ExecuteAutonomousBurn {
+            burn_operator,
+            ..self.burn_accounts(executor, payer, token_account, x402_signature)
+        };
+        execute_burn_instruction(accounts, mode, x402_signature)
+    }
+
+    /// Accounts for a `VerifiedTransfer` burn from `token_account`, for tests
+    /// that swap in payment, credit or fee accounts.
+    pub fn burn_accounts(
+        &self,
+        executor: &Pubkey,
+        payer: &Pubkey,
+        token_account: &Pubkey,
+        x402_signature: &str,
+    ) -> gigabrain_burn::accounts::ExecuteAutonomousBurn {
+        gigabrain_burn::accounts::ExecuteAutonomousBurn {
+            burn_config: self.burn_config,
+            token_mint: self.token_mint,
+            token_account: *token_account,
+            executor: *executor,
+            global_config: global_config_address().0,
+            burn_operator: None,
+            payer: *payer,
+            payment_receipt: Some(payment_receipt_address(x402_signature)),
+            instructions: sysvar::instructions::ID,
+            token_program: self.token_program,
+            system_program: system_program::ID,
+            payment_source: None,
+            payment_mint: None,
+            payment_treasury: None,
+            payment_token_program: None,
+            credit_account: None,
+            fee_schedule: fee_schedule_address().0,
+            fee_exemption: None,
+            fee_token_account: None,
+            price_feed: self.price_feed,
+            pnl_ledger: None,
+        }
+    }
+
+    /// Accounts for a `VerifiedTransfer` retirement of `token_account` by
+    /// `executor`, who also pays, sending the rent to `destination`.
+    pub fn close_accounts(
+        &self,
+        executor: &Pubkey,
+        token_account: &Pubkey,
+        destination: &Pubkey,
+        x402_signature: &str,
+    ) -> gigabrain_burn::accounts::BurnAllAndClose {
+        gigabrain_burn::accounts::BurnAllAndClose {
+            burn_config: self.burn_config,
+            token_mint: self.token_mint,
+            token_account: *token_account,
+            executor: *executor,
+            burn_operator: None,
+            global_config: global_config_address().0,
+            destination: *destination,
+            payer: *executor,
+            payment_receipt: Some(payment_receipt_address(x402_signature)),
+            instructions: sysvar::instructions::ID,
+            token_program: self.token_program,
+            system_program: system_program::ID,
+            payment_source: None,
+            payment_mint: None,
+            payment_treasury: None,
+            payment_token_program: None,
+            credit_account: None,
+            fee_schedule: fee_schedule_address().0,
+            fee_exemption: None,
+            fee_token_account: None,
+        }
+    }
+
+    /// Instructions for one paid retirement of `token_account` by `executor`:
+    /// the x402 payment and `burn_all_and_close`.
+    pub fn close_instructions(
+        &self,
+        executor: &Pubkey,
+        token_account: &Pubkey,
+        destination: &Pubkey,
+        x402_signature: &str,
+    ) -> Vec<Instruction> {
+        let accounts = self.close_accounts(executor, token_account, destination, x402_signature);
+        vec![
+            self.payment_instruction(executor, PAYMENT_AMOUNT),
+            close_instruction(accounts, x402_signature),
+        ]
+    }
+
+    /// Instructions for one paid, oracle-attested burn of `amount` executed by
+    /// `authority`: the Ed25519 profit report, the x402 payment and the burn.
+    pub fn burn_instructions(
+        &self,
+        authority: &Pubkey,
+        amount: u64,
+        profit_amount: u64,
+        period_id: u64,
+        x402_signature: &str,
+    ) -> Vec<Instruction> {
+        self.burn_instructions_with_mode(
+            authority,
+            BurnMode::Amount { amount },
+            profit_amount,
+            period_id,
+            x402_signature,
+        )
+    }
+
+    /// [`Self::burn_instructions`] sized by an arbitrary `mode`.
+    pub fn burn_instructions_with_mode(
+        &self,
+        authority: &Pubkey,
+        mode: BurnMode,
+        profit_amount: u64,
+        period_id: u64,
+        x402_signature: &str,
+    ) -> Vec<Instruction> {
+        vec![
+            self.profit_attestation(&self.profit_report(profit_amount, period_id)),
+            self.payment_instruction(authority, PAYMENT_AMOUNT),
+            self.burn_instruction(authority, mode, x402_signature),
+        ]
+    }
+}
@@ -0,0 +1,131 @@
+//! Program-wide switch: only the upgrade authority recorded in the program's
+//! ProgramData can create or flip it, and burns run unpaused until it exists.
+
+mod common;
+
+use common::*;
+use gigabrain_burn::ErrorCode;
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    pubkey::Pubkey,
+    signature::Signer,
+};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+
+#[tokio::test]
+async fn burns_before_global_config_exists() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    assert!(context
+        .banks_client
+        .get_account(global_config_address().0)
+        .await
+        .unwrap()
+        .is_none());
+
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-no-global");
+    process(&mut context, &instructions, &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn rejects_global_config_at_another_address() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let mut instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-fake-global");
+    for meta in instructions[2].accounts.iter_mut() {
+        if meta.pubkey == global_config_address().0 {
+            meta.pubkey = Pubkey::new_unique();
+        }
+    }
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_anchor_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds);
+}
+
+#[tokio::test]
+async fn upgrade_authority_initializes_global_config() {
+    let (mut context, _) = setup().await;
+    let admin = context.payer.pubkey();
+    set_upgrade_authority(&mut context, Some(admin));
+
+    let instruction = initialize_global_config_instruction(&admin);
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(global_config_address().0)
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn rejects_global_config_from_another_wallet() {
+    let (mut context, _) = setup().await;
+    let admin = context.payer.pubkey();
+    set_upgrade_authority(&mut context, Some(Pubkey::new_unique()));
+
+    let instruction = initialize_global_config_instruction(&admin);
+    let result = process(&mut context, &[instruction], &[]).await;
+
+    assert_program_error(result, ErrorCode::NotUpgradeAuthority);
+}
+
+#[tokio::test]
+async fn rejects_global_config_on_immutable_program() {
+    let (mut context, _) = setup().await;
+    let admin = context.payer.pubkey();
+    set_upgrade_authority(&mut context, None);
+
+    let instruction = initialize_global_config_instruction(&admin);
+    let result = process(&mut context, &[instruction], &[]).await;
+
+    assert_program_error(result, ErrorCode::NotUpgradeAuthority);
+}
+
+#[tokio::test]
+async fn rejects_program_data_at_another_address() {
+    let (mut context, _) = setup().await;
+    let admin = context.payer.pubkey();
+
+    // A well-formed ProgramData naming the signer, but not this program's
+    let fake_program_data = Pubkey::new_unique();
+    let state = UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(admin),
+    };
+    let account = Account::new_data(1_000_000_000, &state, &bpf_loader_upgradeable::ID).unwrap();
+    context.set_account(&fake_program_data, &AccountSharedData::from(account));
+
+    let mut instruction = initialize_global_config_instruction(&admin);
+    for meta in instruction.accounts.iter_mut() {
+        if meta.pubkey == program_data_address() {
+            meta.pubkey = fake_program_data;
+        }
+    }
+    let result = process(&mut context, &[instruction], &[]).await;
+
+    assert_anchor_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds);
+}
+
+#[tokio::test]
+async fn only_upgrade_authority_flips_global_pause() {
+    let (mut context, _) = setup().await;
+    let admin = context.payer.pubkey();
+    set_upgrade_authority(&mut context, Some(admin));
+    let instruction = initialize_global_config_instruction(&admin);
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    // After an upgrade-authority rotation the old admin loses the switch
+    set_upgrade_authority(&mut context, Some(Pubkey::new_unique()));
+    let instruction = set_global_paused_instruction(&admin, true);
+    let result = process(&mut context, &[instruction], &[]).await;
+
+    assert_program_error(result, ErrorCode::NotUpgradeAuthority);
+}
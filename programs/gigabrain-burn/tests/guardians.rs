@@ -0,0 +1,154 @@
+//! Guardian quorum: burns of `quorum_burn_threshold` or more need
+//! `guardian_quorum` distinct guardian signatures, passed as signer remaining
+//! accounts.
+
+mod common;
+
+use common::*;
+use gigabrain_burn::ErrorCode;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+const GUARDIAN_QUORUM: u8 = 2;
+
+async fn setup_guardians() -> (ProgramTestContext, BurnFixture, Vec<Keypair>) {
+    let (mut context, fixture) = setup().await;
+    let guardians: Vec<_> = (0..3).map(|_| Keypair::new()).collect();
+    for guardian in &guardians {
+        fixture
+            .update(
+                &mut context,
+                gigabrain_burn::instruction::AddGuardian {
+                    guardian: guardian.pubkey(),
+                },
+            )
+            .await
+            .unwrap();
+    }
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetGuardianQuorum {
+                guardian_quorum: GUARDIAN_QUORUM,
+                quorum_burn_threshold: AMOUNT,
+            },
+        )
+        .await
+        .unwrap();
+    (context, fixture, guardians)
+}
+
+/// The burn instructions with `signers` appended to the burn as guardian
+/// remaining accounts.
+fn cosigned_burn(
+    fixture: &BurnFixture,
+    authority: &Pubkey,
+    period_id: u64,
+    x402_signature: &str,
+    signers: &[&Keypair],
+) -> Vec<Instruction> {
+    let mut instructions =
+        fixture.burn_instructions(authority, AMOUNT, PROFIT_AMOUNT, period_id, x402_signature);
+    let burn = instructions.last_mut().unwrap();
+    burn.accounts.extend(
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(signer.pubkey(), true)),
+    );
+    instructions
+}
+
+#[tokio::test]
+async fn rejects_large_burn_without_quorum() {
+    let (mut context, fixture, guardians) = setup_guardians().await;
+    let authority = context.payer.pubkey();
+
+    let instructions = cosigned_burn(
+        &fixture,
+        &authority,
+        1,
+        "x402-one-guardian",
+        &[&guardians[0]],
+    );
+    let result = process(&mut context, &instructions, &[&guardians[0]]).await;
+    assert_program_error(result, ErrorCode::QuorumNotMet);
+
+    let instructions = cosigned_burn(
+        &fixture,
+        &authority,
+        1,
+        "x402-two-guardians",
+        &[&guardians[0], &guardians[2]],
+    );
+    process(&mut context, &instructions, &[&guardians[0], &guardians[2]])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn counts_repeated_guardian_once() {
+    let (mut context, fixture, guardians) = setup_guardians().await;
+    let authority = context.payer.pubkey();
+
+    let instructions = cosigned_burn(
+        &fixture,
+        &authority,
+        1,
+        "x402-repeated-guardian",
+        &[&guardians[1], &guardians[1]],
+    );
+    let result = process(&mut context, &instructions, &[&guardians[1]]).await;
+
+    assert_program_error(result, ErrorCode::QuorumNotMet);
+}
+
+#[tokio::test]
+async fn ignores_signers_off_the_roster() {
+    let (mut context, fixture, guardians) = setup_guardians().await;
+    let authority = context.payer.pubkey();
+    let outsider = Keypair::new();
+
+    let instructions = cosigned_burn(
+        &fixture,
+        &authority,
+        1,
+        "x402-outsider",
+        &[&guardians[0], &outsider],
+    );
+    let result = process(&mut context, &instructions, &[&guardians[0], &outsider]).await;
+
+    assert_program_error(result, ErrorCode::QuorumNotMet);
+}
+
+#[tokio::test]
+async fn rejects_removal_that_breaks_quorum() {
+    let (mut context, fixture, guardians) = setup_guardians().await;
+
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::RemoveGuardian {
+                guardian: guardians[0].pubkey(),
+            },
+        )
+        .await
+        .unwrap();
+
+    // Two guardians left for a quorum of two
+    let result = fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::RemoveGuardian {
+                guardian: guardians[1].pubkey(),
+            },
+        )
+        .await;
+    assert_program_error(result, ErrorCode::InvalidQuorum);
+    assert_eq!(fixture.config(&mut context).await.guardians.len(), 2);
+}
@@ -0,0 +1,203 @@
+//! High-water mark: burns are owed only on cumulative profit above the best
+//! level already burned against, and recorded losses lower the mark by the
+//! config's `drawdown_reset_bps` share of the drawdown.
+
+mod common;
+
+use anchor_lang::{AnchorSerialize, InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::{ErrorCode, RoiReport};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{instruction::Instruction, signature::Signer, sysvar};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const LOSS: u64 = 2 * PROFIT_THRESHOLD;
+
+/// Burn owed on `eligible_profit` above the mark.
+fn owed(eligible_profit: u64) -> u64 {
+    eligible_profit * BURN_PERCENTAGE as u64 / 10_000
+}
+
+async fn set_drawdown_reset(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    drawdown_reset_bps: u16,
+) {
+    fixture
+        .update(
+            context,
+            gigabrain_burn::instruction::UpdateBurnConfig {
+                new_profit_threshold: None,
+                new_burn_percentage: None,
+                new_min_burn_amount: None,
+                new_payment_amount: None,
+                new_profit_oracle: None,
+                new_auto_pause_bps: None,
+                new_payment_mode: None,
+                new_profit_mode: None,
+                new_drawdown_reset_bps: Some(drawdown_reset_bps),
+                new_profit_source: None,
+            },
+        )
+        .await
+        .unwrap();
+}
+
+/// Record an oracle-attested period that lost `loss` without burning.
+async fn record_loss(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    loss: u64,
+    period_id: u64,
+) -> Result<(), BanksClientError> {
+    let report = RoiReport {
+        burn_config: fixture.burn_config,
+        cost_basis: loss + 1_000_000,
+        proceeds: 1_000_000,
+        period_id,
+        expiry_slot: u64::MAX,
+    };
+    let instructions = [
+        ed25519_instruction(&fixture.profit_oracle, &report.try_to_vec().unwrap()),
+        Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::RecordProfitPeriod {
+                burn_config: fixture.burn_config,
+                token_mint: fixture.token_mint,
+                authority: context.payer.pubkey(),
+                instructions: sysvar::instructions::ID,
+            }
+            .to_account_metas(None),
+            data: gigabrain_burn::instruction::RecordProfitPeriod {}.data(),
+        },
+    ];
+    process(context, &instructions, &[]).await
+}
+
+#[tokio::test]
+async fn advances_mark_with_each_burn() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let instructions = fixture.burn_instructions(
+        &authority,
+        owed(PROFIT_AMOUNT),
+        PROFIT_AMOUNT,
+        1,
+        "x402-mark-1",
+    );
+    process(&mut context, &instructions, &[]).await.unwrap();
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.cumulative_profit, PROFIT_AMOUNT as i64);
+    assert_eq!(config.high_water_mark, PROFIT_AMOUNT as i64);
+
+    let instructions = fixture.burn_instructions(
+        &authority,
+        owed(PROFIT_THRESHOLD),
+        PROFIT_THRESHOLD,
+        2,
+        "x402-mark-2",
+    );
+    process(&mut context, &instructions, &[]).await.unwrap();
+    let config = fixture.config(&mut context).await;
+    assert_eq!(
+        config.high_water_mark,
+        (PROFIT_AMOUNT + PROFIT_THRESHOLD) as i64
+    );
+    assert_eq!(config.total_burned, owed(PROFIT_AMOUNT + PROFIT_THRESHOLD));
+}
+
+#[tokio::test]
+async fn rejects_burn_below_mark() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let instructions = fixture.burn_instructions(
+        &authority,
+        owed(PROFIT_AMOUNT),
+        PROFIT_AMOUNT,
+        1,
+        "x402-peak",
+    );
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    // Without a reset the whole drawdown must be earned back first
+    record_loss(&mut context, &fixture, LOSS, 2).await.unwrap();
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.cumulative_profit, (PROFIT_AMOUNT - LOSS) as i64);
+    assert_eq!(config.high_water_mark, PROFIT_AMOUNT as i64);
+
+    let instructions = fixture.burn_instructions(
+        &authority,
+        owed(PROFIT_THRESHOLD),
+        PROFIT_THRESHOLD,
+        3,
+        "x402-recovering",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::NoProfitAboveHighWaterMark);
+
+    // Only the part of a recovery above the old peak is owed a burn
+    let recovery = LOSS + PROFIT_THRESHOLD;
+    let instructions = fixture.burn_instructions(
+        &authority,
+        owed(PROFIT_THRESHOLD) - 1,
+        recovery,
+        4,
+        "x402-underburn",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::InsufficientBurnAmount);
+
+    let instructions = fixture.burn_instructions(
+        &authority,
+        owed(PROFIT_THRESHOLD),
+        recovery,
+        4,
+        "x402-new-peak",
+    );
+    process(&mut context, &instructions, &[]).await.unwrap();
+    assert_eq!(
+        fixture.config(&mut context).await.high_water_mark,
+        (PROFIT_AMOUNT + PROFIT_THRESHOLD) as i64
+    );
+}
+
+#[tokio::test]
+async fn drawdown_reset_lowers_mark() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    set_drawdown_reset(&mut context, &fixture, 5000).await;
+    let instructions = fixture.burn_instructions(
+        &authority,
+        owed(PROFIT_AMOUNT),
+        PROFIT_AMOUNT,
+        1,
+        "x402-reset-peak",
+    );
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    // Half of the drawdown comes off the mark
+    record_loss(&mut context, &fixture, LOSS, 2).await.unwrap();
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.cumulative_profit, (PROFIT_AMOUNT - LOSS) as i64);
+    assert_eq!(config.high_water_mark, (PROFIT_AMOUNT - LOSS / 2) as i64);
+
+    // So a recovery past the lowered mark is owed a burn again
+    let instructions =
+        fixture.burn_instructions(&authority, owed(LOSS / 2), LOSS, 3, "x402-reset-recovery");
+    process(&mut context, &instructions, &[]).await.unwrap();
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.high_water_mark, PROFIT_AMOUNT as i64);
+    assert_eq!(config.total_burned, owed(PROFIT_AMOUNT + LOSS / 2));
+}
+
+#[tokio::test]
+async fn rejects_recorded_period_reuse() {
+    let (mut context, fixture) = setup().await;
+    record_loss(&mut context, &fixture, LOSS, 1).await.unwrap();
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = record_loss(&mut context, &fixture, LOSS, 1).await;
+
+    assert_program_error(result, ErrorCode::ProfitPeriodAlreadyUsed);
+}
@@ -0,0 +1,181 @@
+//! Migrating configs written by the original program, which lived at
+//! `["burn_config", token_mint]` with an 8-field layout.
+
+mod common;
+
+use anchor_lang::{Discriminator, InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::{BurnConfig, ErrorCode};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+};
+
+const LEGACY_TOTAL_BURNED: u64 = 7_000_000;
+const LEGACY_BURN_COUNT: u64 = 3;
+
+struct Legacy {
+    token_mint: Pubkey,
+    legacy_config: Pubkey,
+    payment_mint: Pubkey,
+    payment_treasury: Pubkey,
+}
+
+/// A baseline-layout config owned by `authority`, plus payment accounts.
+async fn setup_legacy(context: &mut ProgramTestContext) -> Legacy {
+    let authority = context.payer.pubkey();
+    let token_mint = create_mint(context, &spl_token::ID, &[]).await;
+    let (legacy_config, bump) =
+        Pubkey::find_program_address(&[b"burn_config", token_mint.as_ref()], &gigabrain_burn::ID);
+
+    let mut data = BurnConfig::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(authority.as_ref());
+    data.extend_from_slice(token_mint.as_ref());
+    data.extend_from_slice(&PROFIT_THRESHOLD.to_le_bytes());
+    data.extend_from_slice(&BURN_PERCENTAGE.to_le_bytes());
+    data.extend_from_slice(&MIN_BURN_AMOUNT.to_le_bytes());
+    data.extend_from_slice(&LEGACY_TOTAL_BURNED.to_le_bytes());
+    data.extend_from_slice(&LEGACY_BURN_COUNT.to_le_bytes());
+    data.push(bump);
+    assert_eq!(data.len(), 8 + 99);
+    context.set_account(
+        &legacy_config,
+        &AccountSharedData::from(Account {
+            lamports: 10_000_000,
+            data,
+            owner: gigabrain_burn::ID,
+            ..Account::default()
+        }),
+    );
+
+    let payment_mint = create_mint(context, &spl_token::ID, &[]).await;
+    let payment_treasury = create_token_account(
+        context,
+        &spl_token::ID,
+        &payment_mint,
+        &[],
+        &Pubkey::new_unique(),
+        0,
+    )
+    .await;
+
+    Legacy {
+        token_mint,
+        legacy_config,
+        payment_mint,
+        payment_treasury,
+    }
+}
+
+fn migrate_instruction(legacy: &Legacy, authority: &Pubkey, profit_oracle: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::MigrateBurnConfig {
+            legacy_config: legacy.legacy_config,
+            burn_config: BurnConfig::pda(authority, &legacy.token_mint, CONFIG_ID).0,
+            token_mint: legacy.token_mint,
+            payment_mint: legacy.payment_mint,
+            payment_treasury: legacy.payment_treasury,
+            authority: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::MigrateBurnConfig {
+            config_id: CONFIG_ID,
+            payment_amount: PAYMENT_AMOUNT,
+            profit_oracle: *profit_oracle,
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn migrates_baseline_config_and_burns() {
+    let mut context = program_test().start_with_context().await;
+    let legacy = setup_legacy(&mut context).await;
+    let authority = context.payer.pubkey();
+    let profit_oracle = Keypair::new();
+
+    let instruction = migrate_instruction(&legacy, &authority, &profit_oracle.pubkey());
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(legacy.legacy_config)
+        .await
+        .unwrap()
+        .is_none());
+
+    let token_account = create_token_account(
+        &mut context,
+        &spl_token::ID,
+        &legacy.token_mint,
+        &[],
+        &authority,
+        INITIAL_BALANCE,
+    )
+    .await;
+    let payment_source = create_token_account(
+        &mut context,
+        &spl_token::ID,
+        &legacy.payment_mint,
+        &[],
+        &authority,
+        INITIAL_BALANCE,
+    )
+    .await;
+    let fixture = BurnFixture {
+        token_program: spl_token::ID,
+        token_mint: legacy.token_mint,
+        token_account,
+        payment_mint: legacy.payment_mint,
+        payment_source,
+        payment_treasury: legacy.payment_treasury,
+        burn_config: BurnConfig::pda(&authority, &legacy.token_mint, CONFIG_ID).0,
+        profit_oracle,
+        price_feed: None,
+    };
+
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.authority, authority);
+    assert_eq!(config.creator, authority);
+    assert_eq!(config.token_mint, legacy.token_mint);
+    assert_eq!(config.profit_threshold, PROFIT_THRESHOLD);
+    assert_eq!(config.burn_percentage, BURN_PERCENTAGE);
+    assert_eq!(config.min_burn_amount, MIN_BURN_AMOUNT);
+    assert_eq!(config.total_burned, LEGACY_TOTAL_BURNED);
+    assert_eq!(config.burn_count, LEGACY_BURN_COUNT);
+    assert_eq!(config.payment_treasury, legacy.payment_treasury);
+    assert_eq!(config.profit_oracle, fixture.profit_oracle.pubkey());
+    assert_eq!(config.vault, None);
+    assert!(!config.paused);
+
+    let profit_amount = 4 * PROFIT_THRESHOLD;
+    let amount = profit_amount * BURN_PERCENTAGE as u64 / 10_000;
+    let instructions =
+        fixture.burn_instructions(&authority, amount, profit_amount, 1, "x402-migrated");
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.total_burned, LEGACY_TOTAL_BURNED + amount);
+    assert_eq!(config.burn_count, LEGACY_BURN_COUNT + 1);
+}
+
+#[tokio::test]
+async fn rejects_migration_by_another_wallet() {
+    let mut context = program_test().start_with_context().await;
+    let legacy = setup_legacy(&mut context).await;
+    let intruder = Keypair::new();
+    let fund =
+        system_instruction::transfer(&context.payer.pubkey(), &intruder.pubkey(), 1_000_000_000);
+    process(&mut context, &[fund], &[]).await.unwrap();
+
+    let instruction = migrate_instruction(&legacy, &intruder.pubkey(), &Pubkey::new_unique());
+    let result = process(&mut context, &[instruction], &[&intruder]).await;
+
+    assert_program_error(result, ErrorCode::NotConfigAuthority);
+}
@@ -0,0 +1,112 @@
+//! Oracle-priced burns: profit quoted in another asset, converted through a
+//! mock Pyth price account.
+
+mod common;
+
+use common::*;
+use gigabrain_burn::ErrorCode;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    clock::Clock,
+    pubkey::Pubkey,
+    signature::Signer,
+};
+
+/// USDC-style quote asset
+const QUOTE_DECIMALS: u8 = 6;
+const PRICE_EXPO: i32 = -8;
+/// $0.50 per token
+const PRICE: i64 = 50_000_000;
+const MAX_PRICE_AGE: i64 = 60;
+const MAX_PRICE_CONFIDENCE_BPS: u16 = 100;
+
+/// 4 USDC of profit buys 8 tokens at $0.50, so a 25% burn is 2 tokens.
+const PROFIT_AMOUNT: u64 = 4_000_000;
+const EXPECTED_BURN: u64 = 2_000_000;
+
+async fn setup_price_feed(
+    price_account: impl FnOnce(i64) -> Account,
+) -> (ProgramTestContext, BurnFixture) {
+    let (mut context, mut fixture) = setup().await;
+
+    let now = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let price_feed = Pubkey::new_unique();
+    context.set_account(&price_feed, &AccountSharedData::from(price_account(now)));
+    fixture
+        .set_price_feed(
+            &mut context,
+            price_feed,
+            QUOTE_DECIMALS,
+            MAX_PRICE_AGE,
+            MAX_PRICE_CONFIDENCE_BPS,
+        )
+        .await;
+
+    (context, fixture)
+}
+
+#[tokio::test]
+async fn burns_profit_converted_at_oracle_price() {
+    let (mut context, fixture) =
+        setup_price_feed(|now| pyth_price_account(PRICE, 10_000, PRICE_EXPO, now)).await;
+    let authority = context.payer.pubkey();
+
+    let instructions =
+        fixture.burn_instructions(&authority, EXPECTED_BURN, PROFIT_AMOUNT, 1, "x402-oracle");
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    assert_eq!(
+        mint_supply(&mut context, &fixture.token_mint).await,
+        INITIAL_BALANCE - EXPECTED_BURN
+    );
+}
+
+#[tokio::test]
+async fn rejects_burn_below_oracle_priced_amount() {
+    let (mut context, fixture) =
+        setup_price_feed(|now| pyth_price_account(PRICE, 10_000, PRICE_EXPO, now)).await;
+    let authority = context.payer.pubkey();
+
+    // Enough if profit were counted in tokens, half of what the price implies
+    let amount = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+    let instructions =
+        fixture.burn_instructions(&authority, amount, PROFIT_AMOUNT, 1, "x402-oracle-short");
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::InsufficientBurnAmount);
+}
+
+#[tokio::test]
+async fn rejects_stale_price() {
+    let (mut context, fixture) = setup_price_feed(|now| {
+        pyth_price_account(PRICE, 10_000, PRICE_EXPO, now - 2 * MAX_PRICE_AGE)
+    })
+    .await;
+    let authority = context.payer.pubkey();
+
+    let instructions =
+        fixture.burn_instructions(&authority, EXPECTED_BURN, PROFIT_AMOUNT, 1, "x402-stale");
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::StalePrice);
+}
+
+#[tokio::test]
+async fn rejects_wide_confidence_interval() {
+    // 2% confidence against a 1% limit
+    let (mut context, fixture) =
+        setup_price_feed(|now| pyth_price_account(PRICE, PRICE as u64 / 50, PRICE_EXPO, now)).await;
+    let authority = context.payer.pubkey();
+
+    let instructions =
+        fixture.burn_instructions(&authority, EXPECTED_BURN, PROFIT_AMOUNT, 1, "x402-wide");
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::PriceConfidenceTooWide);
+}
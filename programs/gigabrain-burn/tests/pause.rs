@@ -0,0 +1,106 @@
+//! Circuit breakers: per-config and program-wide pause, and the auto-pause
+//! tripped by an outsized burn.
+
+mod common;
+
+use common::*;
+use gigabrain_burn::ErrorCode;
+use solana_sdk::signature::Signer;
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+
+#[tokio::test]
+async fn rejects_burn_while_config_paused() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetPaused { paused: true },
+        )
+        .await
+        .unwrap();
+
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-paused");
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::ConfigPaused);
+
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::SetPaused { paused: false },
+        )
+        .await
+        .unwrap();
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-unpaused");
+    process(&mut context, &instructions, &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn rejects_burn_while_program_paused() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    set_upgrade_authority(&mut context, Some(authority));
+    let instructions = [
+        initialize_global_config_instruction(&authority),
+        set_global_paused_instruction(&authority, true),
+    ];
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-global");
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::ProgramPaused);
+
+    let unpause = set_global_paused_instruction(&authority, false);
+    process(&mut context, &[unpause], &[]).await.unwrap();
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-global-resumed");
+    process(&mut context, &instructions, &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn auto_pauses_after_outsized_burn() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    // Trip on any burn above 1% of the source balance
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::UpdateBurnConfig {
+                new_profit_threshold: None,
+                new_burn_percentage: None,
+                new_min_burn_amount: None,
+                new_payment_amount: None,
+                new_profit_oracle: None,
+                new_auto_pause_bps: Some(100),
+                new_payment_mode: None,
+                new_profit_mode: None,
+                new_drawdown_reset_bps: None,
+                new_profit_source: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    // Exactly 1% of the balance does not trip it
+    assert_eq!(INITIAL_BALANCE / 100, AMOUNT);
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-at-limit");
+    process(&mut context, &instructions, &[]).await.unwrap();
+    assert!(!fixture.config(&mut context).await.paused);
+
+    // The outsized burn itself succeeds and pauses the config
+    let instructions =
+        fixture.burn_instructions(&authority, 2 * AMOUNT, PROFIT_AMOUNT, 2, "x402-outsized");
+    process(&mut context, &instructions, &[]).await.unwrap();
+    assert!(fixture.config(&mut context).await.paused);
+
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 3, "x402-after-trip");
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::ConfigPaused);
+}
@@ -0,0 +1,64 @@
+//! Payment receipts: every x402 proof is recorded permanently, so it can only
+//! ever fund one burn.
+
+mod common;
+
+use anchor_lang::AccountDeserialize;
+use common::*;
+use gigabrain_burn::PaymentReceipt;
+use solana_program_test::BanksClientError;
+use solana_sdk::{
+    instruction::InstructionError, signature::Signer, system_instruction::SystemError,
+    transaction::TransactionError,
+};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+const X402_SIGNATURE: &str = "x402-receipt";
+
+#[tokio::test]
+async fn records_payment_in_receipt() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, X402_SIGNATURE);
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(payment_receipt_address(X402_SIGNATURE))
+        .await
+        .unwrap()
+        .unwrap();
+    let receipt = PaymentReceipt::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(receipt.payer, authority);
+    assert_eq!(receipt.amount, PAYMENT_AMOUNT);
+    assert_eq!(receipt.burn_config, fixture.burn_config);
+}
+
+#[tokio::test]
+async fn rejects_second_burn_with_same_payment_proof() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, X402_SIGNATURE);
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    // A fresh transfer and profit period, but the proof was already used
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 2, X402_SIGNATURE);
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert!(matches!(
+        result,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(2, InstructionError::Custom(code))
+        )) if code == SystemError::AccountAlreadyInUse as u32
+    ));
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE - AMOUNT
+    );
+}
@@ -0,0 +1,146 @@
+//! Realized PnL ledger: unconsumed trades are never overwritten, and
+//! settling a losing range feeds the loss through the drawdown policy.
+
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::{ErrorCode, PnlLedger};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signer, system_program};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+
+fn pnl_ledger_address(burn_config: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"pnl_ledger", burn_config.as_ref()], &gigabrain_burn::ID).0
+}
+
+async fn setup_ledger() -> (ProgramTestContext, BurnFixture) {
+    let (mut context, fixture) = setup().await;
+
+    let instruction = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::InitializePnlLedger {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            quote_mint: fixture.payment_mint,
+            pnl_ledger: pnl_ledger_address(&fixture.burn_config),
+            authority: context.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::InitializePnlLedger {}.data(),
+    };
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    (context, fixture)
+}
+
+fn record_trade_instruction(
+    fixture: &BurnFixture,
+    recorder: &Pubkey,
+    entry_amount: u64,
+    exit_amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::RecordTrade {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            pnl_ledger: pnl_ledger_address(&fixture.burn_config),
+            quote_mint: fixture.payment_mint,
+            recorder: *recorder,
+            burn_operator: None,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::RecordTrade {
+            entry_amount,
+            exit_amount,
+        }
+        .data(),
+    }
+}
+
+fn settle_instruction(fixture: &BurnFixture, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::SettlePnlLedger {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            pnl_ledger: pnl_ledger_address(&fixture.burn_config),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::SettlePnlLedger {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn rejects_trade_once_ledger_is_full() {
+    let (mut context, fixture) = setup_ledger().await;
+    let authority = context.payer.pubkey();
+
+    for batch in 0..PnlLedger::CAPACITY / 16 {
+        let instructions: Vec<_> = (0..16)
+            .map(|i| record_trade_instruction(&fixture, &authority, 100, 100 + batch * 16 + i))
+            .collect();
+        process(&mut context, &instructions, &[]).await.unwrap();
+    }
+
+    let instruction = record_trade_instruction(&fixture, &authority, 100, 200);
+    let result = process(&mut context, &[instruction], &[]).await;
+    assert_program_error(result, ErrorCode::PnlLedgerFull);
+
+    // Settling consumes the whole range and frees the buffer
+    let settle = settle_instruction(&fixture, &authority);
+    process(&mut context, &[settle], &[]).await.unwrap();
+    let instruction = record_trade_instruction(&fixture, &authority, 100, 300);
+    process(&mut context, &[instruction], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn settles_net_loss_through_drawdown_policy() {
+    let (mut context, fixture) = setup_ledger().await;
+    let authority = context.payer.pubkey();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::UpdateBurnConfig {
+                new_profit_threshold: None,
+                new_burn_percentage: None,
+                new_min_burn_amount: None,
+                new_payment_amount: None,
+                new_profit_oracle: None,
+                new_auto_pause_bps: None,
+                new_payment_mode: None,
+                new_profit_mode: None,
+                new_drawdown_reset_bps: Some(5000),
+                new_profit_source: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    // An attested burn lifts the high-water mark to the burned profit
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-ledger-mark");
+    process(&mut context, &instructions, &[]).await.unwrap();
+    assert_eq!(
+        fixture.config(&mut context).await.high_water_mark,
+        PROFIT_AMOUNT as i64
+    );
+
+    let instructions = [
+        record_trade_instruction(&fixture, &authority, 3_000_000, 2_000_000),
+        record_trade_instruction(&fixture, &authority, 1_000_000, 0),
+    ];
+    process(&mut context, &instructions, &[]).await.unwrap();
+    let settle = settle_instruction(&fixture, &authority);
+    process(&mut context, &[settle], &[]).await.unwrap();
+
+    // A 2_000_000 loss, half of which comes off the mark
+    let config = fixture.config(&mut context).await;
+    assert_eq!(config.cumulative_profit, PROFIT_AMOUNT as i64 - 2_000_000);
+    assert_eq!(config.high_water_mark, PROFIT_AMOUNT as i64 - 1_000_000);
+}
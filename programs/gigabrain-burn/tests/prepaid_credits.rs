@@ -0,0 +1,360 @@
+//! `PaymentMode::PrepaidCredits`: burns debit a credit account funded
+//! ahead of time, and only spent credits can be swept to the treasury.
+
+mod common;
+
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::{BurnMode, CreditAccount, ErrorCode, PaymentMode};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+const DEPOSIT: u64 = 3 * PAYMENT_AMOUNT;
+
+struct Credits {
+    vault: Pubkey,
+    account: Pubkey,
+}
+
+/// A `PrepaidCredits` config whose authority holds a credit account
+/// funded with `deposit`.
+async fn setup_credits(deposit: u64) -> (ProgramTestContext, BurnFixture, Credits) {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::UpdateBurnConfig {
+                new_profit_threshold: None,
+                new_burn_percentage: None,
+                new_min_burn_amount: None,
+                new_payment_amount: None,
+                new_profit_oracle: None,
+                new_auto_pause_bps: None,
+                new_payment_mode: Some(PaymentMode::PrepaidCredits),
+                new_profit_mode: None,
+                new_drawdown_reset_bps: None,
+                new_profit_source: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let credits = Credits {
+        vault: Pubkey::find_program_address(
+            &[b"credit_vault", fixture.burn_config.as_ref()],
+            &gigabrain_burn::ID,
+        )
+        .0,
+        account: Pubkey::find_program_address(
+            &[
+                b"credit_account",
+                fixture.burn_config.as_ref(),
+                authority.as_ref(),
+            ],
+            &gigabrain_burn::ID,
+        )
+        .0,
+    };
+    let instructions = [
+        Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::InitializeCreditVault {
+                burn_config: fixture.burn_config,
+                token_mint: fixture.token_mint,
+                payment_mint: fixture.payment_mint,
+                credit_vault: credits.vault,
+                authority,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: gigabrain_burn::instruction::InitializeCreditVault {}.data(),
+        },
+        Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::OpenCreditAccount {
+                burn_config: fixture.burn_config,
+                token_mint: fixture.token_mint,
+                credit_account: credits.account,
+                owner: authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: gigabrain_burn::instruction::OpenCreditAccount {
+                low_balance_threshold: 0,
+            }
+            .data(),
+        },
+        Instruction {
+            program_id: gigabrain_burn::ID,
+            accounts: gigabrain_burn::accounts::DepositCredits {
+                burn_config: fixture.burn_config,
+                token_mint: fixture.token_mint,
+                credit_account: credits.account,
+                payment_mint: fixture.payment_mint,
+                credit_vault: credits.vault,
+                source: fixture.payment_source,
+                depositor: authority,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: gigabrain_burn::instruction::DepositCredits { amount: deposit }.data(),
+        },
+    ];
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    (context, fixture, credits)
+}
+
+async fn credit_balance(context: &mut ProgramTestContext, credits: &Credits) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(credits.account)
+        .await
+        .unwrap()
+        .unwrap();
+    CreditAccount::try_deserialize(&mut account.data.as_slice())
+        .unwrap()
+        .balance
+}
+
+/// An attested burn paid from the authority's credit account.
+async fn credit_burn(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    credits: &Credits,
+    period_id: u64,
+    x402_signature: &str,
+) -> Result<(), BanksClientError> {
+    let authority = context.payer.pubkey();
+    let accounts = gigabrain_burn::accounts::ExecuteAutonomousBurn {
+        payment_receipt: None,
+        credit_account: Some(credits.account),
+        ..fixture.burn_accounts(
+            &authority,
+            &authority,
+            &fixture.token_account,
+            x402_signature,
+        )
+    };
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, period_id)),
+        execute_burn_instruction(
+            accounts,
+            BurnMode::Amount { amount: AMOUNT },
+            x402_signature,
+        ),
+    ];
+    process(context, &instructions, &[]).await
+}
+
+fn withdraw_instruction(
+    fixture: &BurnFixture,
+    credits: &Credits,
+    owner: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::WithdrawCredits {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            credit_account: credits.account,
+            payment_mint: fixture.payment_mint,
+            credit_vault: credits.vault,
+            destination: *destination,
+            owner: *owner,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::WithdrawCredits { amount }.data(),
+    }
+}
+
+fn sweep_instruction(
+    fixture: &BurnFixture,
+    credits: &Credits,
+    payment_treasury: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::SweepCreditFees {
+            burn_config: fixture.burn_config,
+            token_mint: fixture.token_mint,
+            payment_mint: fixture.payment_mint,
+            credit_vault: credits.vault,
+            payment_treasury: *payment_treasury,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::SweepCreditFees {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn burn_debits_deposited_credits() {
+    let (mut context, fixture, credits) = setup_credits(DEPOSIT).await;
+    assert_eq!(token_balance(&mut context, &credits.vault).await, DEPOSIT);
+
+    credit_burn(&mut context, &fixture, &credits, 1, "x402-credit")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        credit_balance(&mut context, &credits).await,
+        DEPOSIT - PAYMENT_AMOUNT
+    );
+    assert_eq!(
+        fixture.config(&mut context).await.credit_fees_accrued,
+        PAYMENT_AMOUNT
+    );
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE - AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn rejects_burn_with_insufficient_credits() {
+    let (mut context, fixture, credits) = setup_credits(PAYMENT_AMOUNT - 1).await;
+
+    let result = credit_burn(&mut context, &fixture, &credits, 1, "x402-broke").await;
+
+    assert_program_error(result, ErrorCode::InsufficientCredits);
+    assert_eq!(
+        credit_balance(&mut context, &credits).await,
+        PAYMENT_AMOUNT - 1
+    );
+}
+
+#[tokio::test]
+async fn rejects_burn_without_credit_account() {
+    let (mut context, fixture, _) = setup_credits(DEPOSIT).await;
+    let authority = context.payer.pubkey();
+
+    let accounts = gigabrain_burn::accounts::ExecuteAutonomousBurn {
+        payment_receipt: None,
+        ..fixture.burn_accounts(
+            &authority,
+            &authority,
+            &fixture.token_account,
+            "x402-nocredit",
+        )
+    };
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        execute_burn_instruction(
+            accounts,
+            BurnMode::Amount { amount: AMOUNT },
+            "x402-nocredit",
+        ),
+    ];
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::CreditAccountMissing);
+}
+
+#[tokio::test]
+async fn withdraws_only_to_owner() {
+    let (mut context, fixture, credits) = setup_credits(DEPOSIT).await;
+    let authority = context.payer.pubkey();
+
+    // A stranger cannot sign for someone else's credits
+    let stranger = Keypair::new();
+    let stranger_account = create_token_account(
+        &mut context,
+        &spl_token::ID,
+        &fixture.payment_mint,
+        &[],
+        &stranger.pubkey(),
+        0,
+    )
+    .await;
+    let instruction = withdraw_instruction(
+        &fixture,
+        &credits,
+        &stranger.pubkey(),
+        &stranger_account,
+        DEPOSIT,
+    );
+    let result = process(&mut context, &[instruction], &[&stranger]).await;
+    assert_anchor_error(result, anchor_lang::error::ErrorCode::ConstraintHasOne);
+    assert_eq!(credit_balance(&mut context, &credits).await, DEPOSIT);
+
+    // The owner cannot take out more than is left
+    let instruction = withdraw_instruction(
+        &fixture,
+        &credits,
+        &authority,
+        &fixture.payment_source,
+        DEPOSIT + 1,
+    );
+    let result = process(&mut context, &[instruction], &[]).await;
+    assert_program_error(result, ErrorCode::InsufficientCredits);
+
+    let instruction = withdraw_instruction(
+        &fixture,
+        &credits,
+        &authority,
+        &fixture.payment_source,
+        DEPOSIT,
+    );
+    process(&mut context, &[instruction], &[]).await.unwrap();
+    assert_eq!(credit_balance(&mut context, &credits).await, 0);
+    assert_eq!(
+        token_balance(&mut context, &fixture.payment_source).await,
+        INITIAL_BALANCE
+    );
+}
+
+#[tokio::test]
+async fn sweeps_only_spent_credits_to_treasury() {
+    let (mut context, fixture, credits) = setup_credits(DEPOSIT).await;
+    credit_burn(&mut context, &fixture, &credits, 1, "x402-spent")
+        .await
+        .unwrap();
+
+    // Anyone may sweep, but only into the config's treasury
+    let elsewhere = create_token_account(
+        &mut context,
+        &spl_token::ID,
+        &fixture.payment_mint,
+        &[],
+        &Pubkey::new_unique(),
+        0,
+    )
+    .await;
+    let instruction = sweep_instruction(&fixture, &credits, &elsewhere);
+    let result = process(&mut context, &[instruction], &[]).await;
+    assert_anchor_error(result, anchor_lang::error::ErrorCode::ConstraintHasOne);
+
+    let instruction = sweep_instruction(&fixture, &credits, &fixture.payment_treasury);
+    process(&mut context, &[instruction], &[]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &fixture.payment_treasury).await,
+        PAYMENT_AMOUNT
+    );
+    assert_eq!(fixture.config(&mut context).await.credit_fees_accrued, 0);
+
+    // Unspent credits stay in the vault for their owner
+    assert_eq!(
+        token_balance(&mut context, &credits.vault).await,
+        DEPOSIT - PAYMENT_AMOUNT
+    );
+    context.get_new_latest_blockhash().await.unwrap();
+    let instruction = sweep_instruction(&fixture, &credits, &fixture.payment_treasury);
+    process(&mut context, &[instruction], &[]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &fixture.payment_treasury).await,
+        PAYMENT_AMOUNT
+    );
+}
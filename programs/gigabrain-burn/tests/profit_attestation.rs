@@ -0,0 +1,116 @@
+//! Oracle profit attestations: only an Ed25519 signature by the config's
+//! profit oracle, verified over data inside that instruction, over an
+//! unexpired report for a fresh period triggers a burn.
+
+mod common;
+
+use anchor_lang::AnchorSerialize;
+use common::*;
+use gigabrain_burn::{BurnMode, ErrorCode, ProfitReport};
+use solana_sdk::{
+    ed25519_program,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+
+/// A paid burn whose attestation is `attestations`.
+fn burn_with(
+    fixture: &BurnFixture,
+    authority: &Pubkey,
+    attestations: Vec<Instruction>,
+    x402_signature: &str,
+) -> Vec<Instruction> {
+    let mut instructions = attestations;
+    instructions.push(fixture.payment_instruction(authority, PAYMENT_AMOUNT));
+    instructions.push(fixture.burn_instruction(
+        authority,
+        BurnMode::Amount { amount: AMOUNT },
+        x402_signature,
+    ));
+    instructions
+}
+
+#[tokio::test]
+async fn rejects_report_signed_by_another_key() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let report = fixture.profit_report(PROFIT_AMOUNT, 1);
+    let forged = ed25519_instruction(&Keypair::new(), &report.try_to_vec().unwrap());
+    let instructions = burn_with(&fixture, &authority, vec![forged], "x402-forged");
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::ProfitAttestationMissing);
+}
+
+#[tokio::test]
+async fn rejects_offsets_into_another_instruction() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    let attacker = Keypair::new();
+    let message = fixture
+        .profit_report(PROFIT_AMOUNT, 1)
+        .try_to_vec()
+        .unwrap();
+
+    // Instruction 0 is the attacker's own valid signature. Instruction 1
+    // carries the oracle's key inline but tells the precompile to read the
+    // key from instruction 0, so it verifies the attacker's signature.
+    let attacker_signed = ed25519_instruction(&attacker, &message);
+    let mut spoofed = ed25519_instruction(&attacker, &message);
+    spoofed.data[16..48].copy_from_slice(fixture.profit_oracle.pubkey().as_ref());
+    // public_key_instruction_index
+    spoofed.data[8..10].copy_from_slice(&0u16.to_le_bytes());
+    assert_eq!(spoofed.program_id, ed25519_program::id());
+
+    let instructions = burn_with(
+        &fixture,
+        &authority,
+        vec![attacker_signed, spoofed],
+        "x402-spoofed",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::ProfitAttestationMissing);
+}
+
+#[tokio::test]
+async fn rejects_reused_profit_period() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 2, "x402-first");
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    let instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 2, "x402-replayed");
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::ProfitPeriodAlreadyUsed);
+}
+
+#[tokio::test]
+async fn rejects_expired_report() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+    context.warp_to_slot(100).unwrap();
+
+    let report = ProfitReport {
+        expiry_slot: 50,
+        ..fixture.profit_report(PROFIT_AMOUNT, 1)
+    };
+    let instructions = burn_with(
+        &fixture,
+        &authority,
+        vec![fixture.profit_attestation(&report)],
+        "x402-expired",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::ProfitAttestationExpired);
+}
@@ -0,0 +1,170 @@
+//! `PaymentMode::ProgramCollected`: the burn itself moves the x402 payment
+//! into the config's treasury, so the two succeed or fail together.
+
+mod common;
+
+use common::*;
+use gigabrain_burn::{BurnMode, ErrorCode, PaymentMode};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signer};
+use spl_token::error::TokenError;
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+
+async fn setup_collected() -> (ProgramTestContext, BurnFixture) {
+    let (mut context, fixture) = setup().await;
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::UpdateBurnConfig {
+                new_profit_threshold: None,
+                new_burn_percentage: None,
+                new_min_burn_amount: None,
+                new_payment_amount: None,
+                new_profit_oracle: None,
+                new_auto_pause_bps: None,
+                new_payment_mode: Some(PaymentMode::ProgramCollected),
+                new_profit_mode: None,
+                new_drawdown_reset_bps: None,
+                new_profit_source: None,
+            },
+        )
+        .await
+        .unwrap();
+    (context, fixture)
+}
+
+/// An attested burn that pays from `payment_source` into `payment_treasury`.
+fn collected_burn_instructions(
+    fixture: &BurnFixture,
+    authority: &Pubkey,
+    payment_source: &Pubkey,
+    payment_treasury: &Pubkey,
+    x402_signature: &str,
+) -> [Instruction; 2] {
+    let accounts = gigabrain_burn::accounts::ExecuteAutonomousBurn {
+        payment_receipt: None,
+        payment_source: Some(*payment_source),
+        payment_mint: Some(fixture.payment_mint),
+        payment_treasury: Some(*payment_treasury),
+        payment_token_program: Some(spl_token::ID),
+        ..fixture.burn_accounts(authority, authority, &fixture.token_account, x402_signature)
+    };
+    [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        execute_burn_instruction(
+            accounts,
+            BurnMode::Amount { amount: AMOUNT },
+            x402_signature,
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn collects_payment_into_treasury() {
+    let (mut context, fixture) = setup_collected().await;
+    let authority = context.payer.pubkey();
+
+    let instructions = collected_burn_instructions(
+        &fixture,
+        &authority,
+        &fixture.payment_source,
+        &fixture.payment_treasury,
+        "x402-collected",
+    );
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    assert_eq!(
+        token_balance(&mut context, &fixture.payment_treasury).await,
+        PAYMENT_AMOUNT
+    );
+    assert_eq!(
+        token_balance(&mut context, &fixture.payment_source).await,
+        INITIAL_BALANCE - PAYMENT_AMOUNT
+    );
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE - AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn rejects_payment_to_another_treasury() {
+    let (mut context, fixture) = setup_collected().await;
+    let authority = context.payer.pubkey();
+    let other_treasury = create_token_account(
+        &mut context,
+        &spl_token::ID,
+        &fixture.payment_mint,
+        &[],
+        &Pubkey::new_unique(),
+        0,
+    )
+    .await;
+
+    let instructions = collected_burn_instructions(
+        &fixture,
+        &authority,
+        &fixture.payment_source,
+        &other_treasury,
+        "x402-elsewhere",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::InvalidPaymentAccount);
+    assert_eq!(token_balance(&mut context, &other_treasury).await, 0);
+}
+
+#[tokio::test]
+async fn rejects_short_payment() {
+    let (mut context, fixture) = setup_collected().await;
+    let authority = context.payer.pubkey();
+    let short_source = create_token_account(
+        &mut context,
+        &spl_token::ID,
+        &fixture.payment_mint,
+        &[],
+        &authority,
+        PAYMENT_AMOUNT - 1,
+    )
+    .await;
+
+    let instructions = collected_burn_instructions(
+        &fixture,
+        &authority,
+        &short_source,
+        &fixture.payment_treasury,
+        "x402-short",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_token_error(result, TokenError::InsufficientFunds);
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE
+    );
+}
+
+#[tokio::test]
+async fn rejects_burn_without_payment_accounts() {
+    let (mut context, fixture) = setup_collected().await;
+    let authority = context.payer.pubkey();
+
+    let accounts = gigabrain_burn::accounts::ExecuteAutonomousBurn {
+        payment_receipt: None,
+        ..fixture.burn_accounts(
+            &authority,
+            &authority,
+            &fixture.token_account,
+            "x402-unpaid",
+        )
+    };
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        execute_burn_instruction(accounts, BurnMode::Amount { amount: AMOUNT }, "x402-unpaid"),
+    ];
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::PaymentAccountsMissing);
+}
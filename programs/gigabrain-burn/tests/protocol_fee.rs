@@ -0,0 +1,339 @@
+//! Protocol fee: once the upgrade authority publishes a `FeeSchedule`, every
+//! burn past the free allowance sends `fee_bps` of the amount to the fee
+//! destination, unless the config authority holds a `FeeExemption`.
+
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use gigabrain_burn::{BurnMode, ErrorCode};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+const FEE_BPS: u16 = 100;
+const FEE: u64 = AMOUNT * FEE_BPS as u64 / 10_000;
+
+struct Fees {
+    destination: Pubkey,
+    /// The fee destination's account for the burned mint
+    token_account: Pubkey,
+}
+
+fn fee_exemption_address(wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"fee_exemption", wallet.as_ref()], &gigabrain_burn::ID).0
+}
+
+/// A config whose authority is also the upgrade authority, under a
+/// `FEE_BPS` schedule with `free_burns` free burns.
+async fn setup_fees(free_burns: u64) -> (ProgramTestContext, BurnFixture, Fees) {
+    let (mut context, fixture) = setup().await;
+    let admin = context.payer.pubkey();
+    set_upgrade_authority(&mut context, Some(admin));
+
+    let destination = Pubkey::new_unique();
+    let instruction =
+        initialize_fee_schedule_instruction(&admin, FEE_BPS, free_burns, &destination);
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    let token_account = create_token_account(
+        &mut context,
+        &fixture.token_program,
+        &fixture.token_mint,
+        &[],
+        &destination,
+        0,
+    )
+    .await;
+
+    (
+        context,
+        fixture,
+        Fees {
+            destination,
+            token_account,
+        },
+    )
+}
+
+/// An attested, paid burn of `AMOUNT` by the authority with the given fee accounts.
+async fn fee_burn(
+    context: &mut ProgramTestContext,
+    fixture: &BurnFixture,
+    fee_token_account: Option<Pubkey>,
+    fee_exemption: Option<Pubkey>,
+    period_id: u64,
+    x402_signature: &str,
+) -> Result<(), BanksClientError> {
+    let authority = context.payer.pubkey();
+    let accounts = gigabrain_burn::accounts::ExecuteAutonomousBurn {
+        fee_token_account,
+        fee_exemption,
+        ..fixture.burn_accounts(
+            &authority,
+            &authority,
+            &fixture.token_account,
+            x402_signature,
+        )
+    };
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, period_id)),
+        fixture.payment_instruction(&authority, PAYMENT_AMOUNT),
+        execute_burn_instruction(
+            accounts,
+            BurnMode::Amount { amount: AMOUNT },
+            x402_signature,
+        ),
+    ];
+    process(context, &instructions, &[]).await
+}
+
+#[tokio::test]
+async fn deducts_fee_from_burn() {
+    let (mut context, fixture, fees) = setup_fees(0).await;
+    let supply_before = mint_supply(&mut context, &fixture.token_mint).await;
+
+    fee_burn(
+        &mut context,
+        &fixture,
+        Some(fees.token_account),
+        None,
+        1,
+        "x402-fee",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(token_balance(&mut context, &fees.token_account).await, FEE);
+    assert_eq!(
+        mint_supply(&mut context, &fixture.token_mint).await,
+        supply_before - (AMOUNT - FEE)
+    );
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE - AMOUNT
+    );
+    assert_eq!(
+        fixture.config(&mut context).await.total_burned,
+        AMOUNT - FEE
+    );
+}
+
+#[tokio::test]
+async fn charges_fee_after_free_burns() {
+    let (mut context, fixture, fees) = setup_fees(1).await;
+
+    // The first burn is free and needs no fee account
+    fee_burn(&mut context, &fixture, None, None, 1, "x402-free")
+        .await
+        .unwrap();
+    assert_eq!(token_balance(&mut context, &fees.token_account).await, 0);
+
+    let result = fee_burn(&mut context, &fixture, None, None, 2, "x402-unfunded").await;
+    assert_program_error(result, ErrorCode::FeeAccountMissing);
+
+    fee_burn(
+        &mut context,
+        &fixture,
+        Some(fees.token_account),
+        None,
+        2,
+        "x402-charged",
+    )
+    .await
+    .unwrap();
+    assert_eq!(token_balance(&mut context, &fees.token_account).await, FEE);
+}
+
+#[tokio::test]
+async fn waives_fee_for_exempt_authority() {
+    let (mut context, fixture, fees) = setup_fees(0).await;
+    let admin = context.payer.pubkey();
+    let exemption = fee_exemption_address(&admin);
+
+    let add = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::AddFeeExemption {
+            fee_exemption: exemption,
+            program_data: program_data_address(),
+            admin,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::AddFeeExemption { wallet: admin }.data(),
+    };
+    process(&mut context, &[add], &[]).await.unwrap();
+
+    fee_burn(
+        &mut context,
+        &fixture,
+        None,
+        Some(exemption),
+        1,
+        "x402-exempt",
+    )
+    .await
+    .unwrap();
+    assert_eq!(token_balance(&mut context, &fees.token_account).await, 0);
+
+    let remove = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: gigabrain_burn::accounts::RemoveFeeExemption {
+            fee_exemption: exemption,
+            program_data: program_data_address(),
+            admin,
+        }
+        .to_account_metas(None),
+        data: gigabrain_burn::instruction::RemoveFeeExemption {}.data(),
+    };
+    process(&mut context, &[remove], &[]).await.unwrap();
+
+    fee_burn(
+        &mut context,
+        &fixture,
+        Some(fees.token_account),
+        None,
+        2,
+        "x402-unexempt",
+    )
+    .await
+    .unwrap();
+    assert_eq!(token_balance(&mut context, &fees.token_account).await, FEE);
+}
+
+#[tokio::test]
+async fn charges_fee_on_retirement() {
+    let (mut context, fixture, fees) = setup_fees(0).await;
+    let authority = context.payer.pubkey();
+    let destination = Pubkey::new_unique();
+    let fee = INITIAL_BALANCE * FEE_BPS as u64 / 10_000;
+
+    let mut instructions = fixture.close_instructions(
+        &authority,
+        &fixture.token_account,
+        &destination,
+        "x402-retire",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+    assert_program_error(result, ErrorCode::FeeAccountMissing);
+
+    let accounts = gigabrain_burn::accounts::BurnAllAndClose {
+        fee_token_account: Some(fees.token_account),
+        ..fixture.close_accounts(
+            &authority,
+            &fixture.token_account,
+            &destination,
+            "x402-retire",
+        )
+    };
+    instructions[1] = close_instruction(accounts, "x402-retire");
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    assert_eq!(token_balance(&mut context, &fees.token_account).await, fee);
+    assert_eq!(
+        fixture.config(&mut context).await.total_burned,
+        INITIAL_BALANCE - fee
+    );
+}
+
+#[tokio::test]
+async fn rejects_fee_account_of_another_owner() {
+    let (mut context, fixture, _) = setup_fees(0).await;
+    let elsewhere = create_token_account(
+        &mut context,
+        &fixture.token_program,
+        &fixture.token_mint,
+        &[],
+        &Pubkey::new_unique(),
+        0,
+    )
+    .await;
+
+    let result = fee_burn(
+        &mut context,
+        &fixture,
+        Some(elsewhere),
+        None,
+        1,
+        "x402-elsewhere",
+    )
+    .await;
+
+    assert_program_error(result, ErrorCode::InvalidFeeAccount);
+}
+
+#[tokio::test]
+async fn rejects_fee_account_of_another_mint() {
+    let (mut context, fixture, fees) = setup_fees(0).await;
+    let wrong_mint = create_token_account(
+        &mut context,
+        &spl_token::ID,
+        &fixture.payment_mint,
+        &[],
+        &fees.destination,
+        0,
+    )
+    .await;
+
+    let result = fee_burn(
+        &mut context,
+        &fixture,
+        Some(wrong_mint),
+        None,
+        1,
+        "x402-wrong-mint",
+    )
+    .await;
+
+    assert_program_error(result, ErrorCode::InvalidFeeAccount);
+}
+
+#[tokio::test]
+async fn rejects_burn_omitting_fee_schedule() {
+    let (mut context, fixture, _) = setup_fees(0).await;
+    let authority = context.payer.pubkey();
+
+    // Leave the schedule out the way an optional account would be
+    let mut burn = fixture.burn_instruction(
+        &authority,
+        BurnMode::Amount { amount: AMOUNT },
+        "x402-no-schedule",
+    );
+    for meta in burn
+        .accounts
+        .iter_mut()
+        .filter(|meta| meta.pubkey == fee_schedule_address().0)
+    {
+        *meta = AccountMeta::new_readonly(gigabrain_burn::ID, false);
+    }
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        fixture.payment_instruction(&authority, PAYMENT_AMOUNT),
+        burn,
+    ];
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_anchor_error(result, anchor_lang::error::ErrorCode::ConstraintSeeds);
+}
+
+#[tokio::test]
+async fn rejects_fee_schedule_from_another_wallet() {
+    let (mut context, _) = setup().await;
+    let stranger = Keypair::new();
+    let admin = context.payer.pubkey();
+    set_upgrade_authority(&mut context, Some(admin));
+
+    let transfer =
+        system_instruction::transfer(&context.payer.pubkey(), &stranger.pubkey(), 1_000_000_000);
+    let instruction =
+        initialize_fee_schedule_instruction(&stranger.pubkey(), FEE_BPS, 0, &stranger.pubkey());
+    let result = process(&mut context, &[transfer, instruction], &[&stranger]).await;
+
+    assert_program_error(result, ErrorCode::NotUpgradeAuthority);
+}
@@ -0,0 +1,162 @@
+//! `ProfitMode::RoiBps`: the oracle attests a period's cost basis and
+//! proceeds, and the profit threshold is read as a return in basis points.
+
+mod common;
+
+use anchor_lang::AnchorSerialize;
+use common::*;
+use gigabrain_burn::{BurnMode, ErrorCode, ProfitMode, RoiReport};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+/// Minimum return on cost basis: 20%
+const ROI_THRESHOLD_BPS: u64 = 2000;
+const COST_BASIS: u64 = 10_000_000;
+
+async fn setup_roi() -> (ProgramTestContext, BurnFixture) {
+    let (mut context, fixture) = setup().await;
+    fixture
+        .update(
+            &mut context,
+            gigabrain_burn::instruction::UpdateBurnConfig {
+                new_profit_threshold: Some(ROI_THRESHOLD_BPS),
+                new_burn_percentage: None,
+                new_min_burn_amount: None,
+                new_payment_amount: None,
+                new_profit_oracle: None,
+                new_auto_pause_bps: None,
+                new_payment_mode: None,
+                new_profit_mode: Some(ProfitMode::RoiBps),
+                new_drawdown_reset_bps: None,
+                new_profit_source: None,
+            },
+        )
+        .await
+        .unwrap();
+    (context, fixture)
+}
+
+fn roi_report(fixture: &BurnFixture, cost_basis: u64, proceeds: u64) -> RoiReport {
+    RoiReport {
+        burn_config: fixture.burn_config,
+        cost_basis,
+        proceeds,
+        period_id: 1,
+        expiry_slot: u64::MAX,
+    }
+}
+
+/// A paid burn of the share owed on `report`, attested by `signer`.
+fn roi_burn(
+    fixture: &BurnFixture,
+    authority: &Pubkey,
+    signer: &Keypair,
+    report: &RoiReport,
+    x402_signature: &str,
+) -> Vec<Instruction> {
+    let profit = report.proceeds.saturating_sub(report.cost_basis);
+    let amount = (profit * BURN_PERCENTAGE as u64 / 10_000).max(MIN_BURN_AMOUNT);
+    vec![
+        ed25519_instruction(signer, &report.try_to_vec().unwrap()),
+        fixture.payment_instruction(authority, PAYMENT_AMOUNT),
+        fixture.burn_instruction(authority, BurnMode::Amount { amount }, x402_signature),
+    ]
+}
+
+#[tokio::test]
+async fn burns_when_roi_meets_threshold() {
+    let (mut context, fixture) = setup_roi().await;
+    let authority = context.payer.pubkey();
+
+    // 30% on cost basis
+    let report = roi_report(&fixture, COST_BASIS, COST_BASIS * 13 / 10);
+    let instructions = roi_burn(
+        &fixture,
+        &authority,
+        &fixture.profit_oracle,
+        &report,
+        "x402-roi",
+    );
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    let profit = COST_BASIS * 3 / 10;
+    let config = fixture.config(&mut context).await;
+    assert_eq!(
+        config.total_burned,
+        profit * BURN_PERCENTAGE as u64 / 10_000
+    );
+    assert_eq!(config.high_water_mark, profit as i64);
+    assert_eq!(config.last_profit_period, 1);
+}
+
+#[tokio::test]
+async fn rejects_roi_below_threshold() {
+    let (mut context, fixture) = setup_roi().await;
+    let authority = context.payer.pubkey();
+
+    // 10% on cost basis; the absolute profit alone would clear PROFIT_THRESHOLD
+    let report = roi_report(&fixture, COST_BASIS, COST_BASIS * 11 / 10);
+    assert!(report.proceeds - report.cost_basis >= PROFIT_THRESHOLD);
+    let instructions = roi_burn(
+        &fixture,
+        &authority,
+        &fixture.profit_oracle,
+        &report,
+        "x402-low-roi",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::ProfitThresholdNotMet);
+}
+
+#[tokio::test]
+async fn rejects_roi_report_signed_by_another_key() {
+    let (mut context, fixture) = setup_roi().await;
+    let authority = context.payer.pubkey();
+
+    let report = roi_report(&fixture, COST_BASIS, COST_BASIS * 13 / 10);
+    let instructions = roi_burn(
+        &fixture,
+        &authority,
+        &Keypair::new(),
+        &report,
+        "x402-roi-forged",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::ProfitAttestationMissing);
+}
+
+#[tokio::test]
+async fn rejects_absolute_report_in_roi_mode() {
+    let (mut context, fixture) = setup_roi().await;
+    let authority = context.payer.pubkey();
+
+    let instructions =
+        fixture.burn_instructions(&authority, 1_000_000, 4_000_000, 1, "x402-roi-absolute");
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::ProfitAttestationMissing);
+}
+
+#[tokio::test]
+async fn rejects_roi_report_without_cost_basis() {
+    let (mut context, fixture) = setup_roi().await;
+    let authority = context.payer.pubkey();
+
+    let report = roi_report(&fixture, 0, COST_BASIS);
+    let instructions = roi_burn(
+        &fixture,
+        &authority,
+        &fixture.profit_oracle,
+        &report,
+        "x402-roi-zero",
+    );
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::InvalidRoiReport);
+}
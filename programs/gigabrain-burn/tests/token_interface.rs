@@ -0,0 +1,47 @@
+//! Burns against classic SPL Token and Token-2022 mints.
+
+mod common;
+
+use common::*;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+use spl_token_2022::extension::ExtensionType;
+
+async fn assert_burns(token_program: Pubkey, mint_extensions: &[ExtensionType]) {
+    let mut context = program_test().start_with_context().await;
+    let fixture = BurnFixture::setup(&mut context, &token_program, mint_extensions).await;
+    let authority = context.payer.pubkey();
+
+    let profit_amount = 4 * PROFIT_THRESHOLD;
+    let amount = profit_amount * BURN_PERCENTAGE as u64 / 10_000;
+    let instructions =
+        fixture.burn_instructions(&authority, amount, profit_amount, 1, "x402-token-interface");
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    assert_eq!(
+        mint_supply(&mut context, &fixture.token_mint).await,
+        INITIAL_BALANCE - amount
+    );
+    assert_eq!(
+        token_balance(&mut context, &fixture.token_account).await,
+        INITIAL_BALANCE - amount
+    );
+    assert_eq!(
+        token_balance(&mut context, &fixture.payment_treasury).await,
+        PAYMENT_AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn burns_classic_spl_token() {
+    assert_burns(spl_token::ID, &[]).await;
+}
+
+#[tokio::test]
+async fn burns_token_2022() {
+    assert_burns(spl_token_2022::ID, &[]).await;
+}
+
+#[tokio::test]
+async fn burns_token_2022_with_transfer_fee_extension() {
+    assert_burns(spl_token_2022::ID, &[ExtensionType::TransferFeeConfig]).await;
+}
@@ -0,0 +1,111 @@
+//! x402 payment verification: the burn must directly follow a sufficient
+//! `transfer_checked` into the config's payment treasury.
+
+mod common;
+
+use common::*;
+use gigabrain_burn::{BurnMode, ErrorCode};
+use solana_program_test::processor;
+use solana_sdk::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    pubkey::Pubkey,
+    signature::Signer,
+};
+
+const PROFIT_AMOUNT: u64 = 4 * PROFIT_THRESHOLD;
+const AMOUNT: u64 = PROFIT_AMOUNT * BURN_PERCENTAGE as u64 / 10_000;
+
+#[tokio::test]
+async fn rejects_burn_without_payment() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        fixture.burn_instruction(&authority, BurnMode::Amount { amount: AMOUNT }, "x402-none"),
+    ];
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::PaymentNotFound);
+}
+
+#[tokio::test]
+async fn rejects_payment_not_directly_before_burn() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let instructions = [
+        fixture.payment_instruction(&authority, PAYMENT_AMOUNT),
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        fixture.burn_instruction(
+            &authority,
+            BurnMode::Amount { amount: AMOUNT },
+            "x402-early",
+        ),
+    ];
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::PaymentNotFound);
+}
+
+#[tokio::test]
+async fn rejects_insufficient_payment() {
+    let (mut context, fixture) = setup().await;
+    let authority = context.payer.pubkey();
+
+    let instructions = [
+        fixture.profit_attestation(&fixture.profit_report(PROFIT_AMOUNT, 1)),
+        fixture.payment_instruction(&authority, PAYMENT_AMOUNT - 1),
+        fixture.burn_instruction(
+            &authority,
+            BurnMode::Amount { amount: AMOUNT },
+            "x402-short",
+        ),
+    ];
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::InsufficientPayment);
+}
+
+/// A program that forwards its instruction to gigabrain-burn through CPI.
+fn forward_to_burn_program(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let instruction = Instruction {
+        program_id: gigabrain_burn::ID,
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: data.to_vec(),
+    };
+    invoke(&instruction, accounts)
+}
+
+#[tokio::test]
+async fn rejects_burn_invoked_through_another_program() {
+    let wrapper = Pubkey::new_unique();
+    let mut program_test = program_test();
+    program_test.add_program("cpi_wrapper", wrapper, processor!(forward_to_burn_program));
+    let mut context = program_test.start_with_context().await;
+    let fixture = BurnFixture::setup(&mut context, &spl_token::ID, &[]).await;
+    let authority = context.payer.pubkey();
+
+    // The transfer directly precedes the wrapper's top-level instruction, so
+    // without the stack height check every burn it forwards would pass
+    let mut instructions =
+        fixture.burn_instructions(&authority, AMOUNT, PROFIT_AMOUNT, 1, "x402-cpi");
+    instructions[2].program_id = wrapper;
+    let result = process(&mut context, &instructions, &[]).await;
+
+    assert_program_error(result, ErrorCode::PaymentRequiresTopLevel);
+}
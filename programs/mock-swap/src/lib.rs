@@ -0,0 +1,114 @@
+//! Minimal stand-in for Jupiter in the `buyback_and_burn` tests.
+//!
+//! A single instruction takes `lamports_in` from the user and pays
+//! `amount_out` tokens from a pool token account owned by the `["pool"]` PDA.
+//! There is no pricing: the caller picks the output, so tests can exercise
+//! the burn program's minimum-out check.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    declare_id,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_instruction, system_program,
+};
+use spl_token_2022::extension::StateWithExtensions;
+
+declare_id!("5BeaYedH4aaWTCatnf5bkr1XtrtQQeSvmrqoTJaHxf4s");
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub const POOL_SEED: &[u8] = b"pool";
+
+/// PDA that owns the pool token account and collects the lamports paid in
+pub fn pool_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_SEED], &ID)
+}
+
+/// Swap `lamports_in` from `user` for `amount_out` tokens into `destination`
+pub fn swap(
+    user: &Pubkey,
+    pool_token_account: &Pubkey,
+    destination: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    lamports_in: u64,
+    amount_out: u64,
+) -> Instruction {
+    let mut data = Vec::with_capacity(16);
+    data.extend_from_slice(&lamports_in.to_le_bytes());
+    data.extend_from_slice(&amount_out.to_le_bytes());
+
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(pool_authority().0, false),
+            AccountMeta::new(*pool_token_account, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+    let user = next_account_info(accounts)?;
+    let pool_authority = next_account_info(accounts)?;
+    let pool_token_account = next_account_info(accounts)?;
+    let destination = next_account_info(accounts)?;
+    let mint = next_account_info(accounts)?;
+    let token_program = next_account_info(accounts)?;
+    let system_program = next_account_info(accounts)?;
+
+    if data.len() != 16 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let lamports_in = u64::from_le_bytes(data[..8].try_into().unwrap());
+    let amount_out = u64::from_le_bytes(data[8..].try_into().unwrap());
+
+    let (expected_authority, bump) = Pubkey::find_program_address(&[POOL_SEED], program_id);
+    if *pool_authority.key != expected_authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    invoke(
+        &system_instruction::transfer(user.key, pool_authority.key, lamports_in),
+        &[user.clone(), pool_authority.clone(), system_program.clone()],
+    )?;
+
+    let decimals = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint.data.borrow())?
+        .base
+        .decimals;
+    invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            pool_token_account.key,
+            mint.key,
+            destination.key,
+            pool_authority.key,
+            &[],
+            amount_out,
+            decimals,
+        )?,
+        &[
+            pool_token_account.clone(),
+            mint.clone(),
+            destination.clone(),
+            pool_authority.clone(),
+            token_program.clone(),
+        ],
+        &[&[POOL_SEED, &[bump]]],
+    )
+}
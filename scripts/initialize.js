@@ -11,7 +11,28 @@ import { Program, AnchorProvider, BN } from '@coral-xyz/anchor';
 import fs from 'fs';
 
 const DEVNET_RPC = process.env.SOLANA_RPC_URL || 'https://api.devnet.solana.com';
-const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID || 'BurnGigaBrain11111111111111111111111111111111');
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID || 'BurnGigaBrain1111111111111111111111111111111');
+
+// Derive a burn config PDA: ["burn_config", authority, token_mint, config_id (u64 LE)]
+function deriveBurnConfigPda(authority, tokenMint, configId = 0) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from('burn_config'),
+      authority.toBuffer(),
+      tokenMint.toBuffer(),
+      new BN(configId).toArrayLike(Buffer, 'le', 8),
+    ],
+    PROGRAM_ID
+  );
+}
+
+// Legacy mint-only PDA, only needed to call migrate_burn_config
+function deriveLegacyBurnConfigPda(tokenMint) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('burn_config'), tokenMint.toBuffer()],
+    PROGRAM_ID
+  );
+}
 
 // Load wallet
 function loadWallet() {
@@ -30,15 +51,13 @@ async function initializeBurnConfig(tokenMint, config) {
   
   console.log(`\n📊 Configuration:`);
   console.log(`   Token Mint: ${tokenMint.toString()}`);
+  console.log(`   Config ID: ${config.configId}`);
   console.log(`   Profit Threshold: ${config.profitThreshold} basis points`);
   console.log(`   Burn Percentage: ${config.burnPercentage / 100}%`);
   console.log(`   Min Burn Amount: ${config.minBurnAmount}`);
   
   // Derive burn config PDA
-  const [burnConfigPda, bump] = PublicKey.findProgramAddressSync(
-    [Buffer.from('burn_config'), tokenMint.toBuffer()],
-    PROGRAM_ID
-  );
+  const [burnConfigPda, bump] = deriveBurnConfigPda(wallet.publicKey, tokenMint, config.configId);
   
   console.log(`\n🔑 Burn Config PDA: ${burnConfigPda.toString()}`);
   
@@ -57,6 +76,7 @@ if (import.meta.url === `file://${process.argv[1]}`) {
   const tokenMint = new PublicKey(process.argv[2] || '11111111111111111111111111111111');
   
   const config = {
+    configId: Number(process.argv[3] || 0),
     profitThreshold: 1000,  // 10% profit (1000 basis points)
     burnPercentage: 2500,   // 25% of profits (2500 = 25%)
     minBurnAmount: 1000000, // 1 token (assuming 6 decimals)
@@ -65,4 +85,4 @@ if (import.meta.url === `file://${process.argv[1]}`) {
   initializeBurnConfig(tokenMint, config).catch(console.error);
 }
 
-export { initializeBurnConfig };
+export { initializeBurnConfig, deriveBurnConfigPda, deriveLegacyBurnConfigPda };
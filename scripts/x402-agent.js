@@ -2,25 +2,31 @@
 
 /**
  * GigaBrain x402 Autonomous Agent
- * 
+ *
  * This agent autonomously:
  * 1. Monitors trading profits
- * 2. Pays for burn service via x402 micropayment ($0.005 USDC)
- * 3. Executes on-chain token burn via Anchor program
- * 4. All without human intervention
+ * 2. Fetches a profit report signed by the config's profit oracle
+ * 3. Pays for burn service via x402 micropayment ($0.005 USDC)
+ * 4. Executes on-chain token burn via Anchor program
+ * 5. All without human intervention
+ *
+ * The oracle attestation, the x402 payment and the burn go out in ONE
+ * transaction: the program reads the Ed25519 report and the payment
+ * `transfer_checked` (which must directly precede the burn) from the
+ * instructions sysvar, and rejects burns reached through CPI.
  */
 
-import { Connection, PublicKey, Keypair, Transaction } from '@solana/web3.js';
-import { Program, AnchorProvider, web3, BN } from '@coral-xyz/anchor';
-import { getAssociatedTokenAddress, createTransferInstruction, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import crypto from 'crypto';
+import { Connection, PublicKey, Keypair, Ed25519Program, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY } from '@solana/web3.js';
+import { Program, AnchorProvider, Wallet, BN } from '@coral-xyz/anchor';
+import { getAssociatedTokenAddressSync, createTransferCheckedInstruction, getMint } from '@solana/spl-token';
 import fs from 'fs';
 
 // Configuration
 const DEVNET_RPC = process.env.SOLANA_RPC_URL || 'https://api.devnet.solana.com';
-const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID || 'BurnGigaBrain11111111111111111111111111111111');
-const USDC_MINT_DEVNET = new PublicKey('4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU');
-const X402_TREASURY = new PublicKey('jawKuQ3xtcYoAuqE9jyG2H35sv2pWJSzsyjoNpsxG38');
-const X402_BURN_FEE = 0.005; // $0.005 USDC per burn
+const PROGRAM_ID = new PublicKey(process.env.PROGRAM_ID || 'BurnGigaBrain1111111111111111111111111111111');
+const IDL_PATH = process.env.IDL_PATH || 'target/idl/gigabrain_burn.json';
+const PROFIT_ORACLE_URL = process.env.PROFIT_ORACLE_URL || 'http://localhost:5000/api/profit-report';
 
 // Load wallet
 function loadWallet() {
@@ -29,83 +35,170 @@ function loadWallet() {
   return Keypair.fromSecretKey(Uint8Array.from(secretKey));
 }
 
-// Create x402 micropayment for burn service
-async function createX402Payment(connection, payer, amountUSD) {
-  console.log(`\n💳 Creating x402 payment: $${amountUSD} USDC`);
-  
-  const microUsdc = Math.floor(amountUSD * 1_000_000);
-  
-  // Get USDC token accounts
-  const payerTokenAccount = await getAssociatedTokenAddress(USDC_MINT_DEVNET, payer.publicKey);
-  const treasuryTokenAccount = await getAssociatedTokenAddress(USDC_MINT_DEVNET, X402_TREASURY);
-  
-  // Create USDC transfer instruction
-  const transferIx = createTransferInstruction(
+function pda(seeds, programId = PROGRAM_ID) {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+// Derive a burn config PDA: ["burn_config", authority, token_mint, config_id (u64 LE)]
+function deriveBurnConfigPda(authority, tokenMint, configId = 0) {
+  return pda([
+    Buffer.from('burn_config'),
+    authority.toBuffer(),
+    tokenMint.toBuffer(),
+    new BN(configId).toArrayLike(Buffer, 'le', 8),
+  ]);
+}
+
+// Receipt PDA the burn creates for this payment: ["payment_receipt", sha256(x402_signature)]
+function derivePaymentReceiptPda(x402Signature) {
+  const digest = crypto.createHash('sha256').update(x402Signature).digest();
+  return pda([Buffer.from('payment_receipt'), digest]);
+}
+
+// Ask the profit oracle to sign this period's ProfitReport for the config
+async function fetchProfitAttestation(burnConfig, profitOracle) {
+  const response = await fetch(PROFIT_ORACLE_URL, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify({ burnConfig: burnConfig.toString() }),
+  });
+  if (!response.ok) {
+    throw new Error(`Profit oracle returned ${response.status}`);
+  }
+  const report = await response.json();
+
+  // Borsh encoding of ProfitReport { burn_config, profit_amount, period_id, expiry_slot }
+  const message = Buffer.concat([
+    burnConfig.toBuffer(),
+    new BN(report.profitAmount).toArrayLike(Buffer, 'le', 8),
+    new BN(report.periodId).toArrayLike(Buffer, 'le', 8),
+    new BN(report.expirySlot).toArrayLike(Buffer, 'le', 8),
+  ]);
+
+  return {
+    profitAmount: report.profitAmount,
+    periodId: report.periodId,
+    instruction: Ed25519Program.createInstructionWithPublicKey({
+      publicKey: profitOracle.toBytes(),
+      message,
+      signature: Buffer.from(report.signature, 'base64'),
+    }),
+  };
+}
+
+// x402 micropayment for burn service, placed directly before the burn
+async function createX402PaymentInstruction(connection, payer, config) {
+  const paymentMint = await getMint(connection, config.paymentMint);
+  const payerTokenAccount = getAssociatedTokenAddressSync(config.paymentMint, payer);
+
+  console.log(`\n💳 x402 payment: ${config.paymentAmount.toString()} to ${config.paymentTreasury.toString()}`);
+
+  return createTransferCheckedInstruction(
     payerTokenAccount,
-    treasuryTokenAccount,
-    payer.publicKey,
-    microUsdc,
-    [],
-    TOKEN_PROGRAM_ID
+    config.paymentMint,
+    config.paymentTreasury,
+    payer,
+    BigInt(config.paymentAmount.toString()),
+    paymentMint.decimals
   );
-  
-  const transaction = new Transaction().add(transferIx);
-  transaction.feePayer = payer.publicKey;
-  transaction.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
-  
-  // Sign and send
-  transaction.sign(payer);
-  const signature = await connection.sendRawTransaction(transaction.serialize());
-  await connection.confirmTransaction(signature);
-  
-  console.log(`✅ x402 Payment Confirmed: ${signature}`);
-  console.log(`   Amount: $${amountUSD} USDC`);
-  console.log(`   Treasury: ${X402_TREASURY.toString()}`);
-  
-  return signature;
+}
+
+// Fee accounts for the program-wide protocol fee, if one is due on this burn
+async function protocolFeeAccounts(program, config, tokenMint, tokenProgram) {
+  const feeSchedule = pda([Buffer.from('fee_schedule')]);
+  const schedule = await program.account.feeSchedule.fetchNullable(feeSchedule);
+  const exemptionPda = pda([Buffer.from('fee_exemption'), config.authority.toBuffer()]);
+  const exemption = await program.account.feeExemption.fetchNullable(exemptionPda);
+
+  const feeDue = schedule && !exemption && schedule.feeBps > 0 && config.burnCount.gte(schedule.freeBurns);
+  return {
+    feeSchedule,
+    feeExemption: exemption ? exemptionPda : null,
+    feeTokenAccount: feeDue
+      ? getAssociatedTokenAddressSync(tokenMint, schedule.feeDestination, true, tokenProgram)
+      : null,
+  };
 }
 
 // Execute autonomous burn via Anchor program
+//
+// `mode` is a BurnMode: { amount: { amount } }, { balanceBps: { bps } } or { all: {} }
 async function executeAutonomousBurn(
   program,
   wallet,
+  configAuthority,
+  configId,
   tokenMint,
-  burnAmount,
-  profitAmount,
-  x402Signature
+  mode
 ) {
+  const connection = program.provider.connection;
+  const burnConfig = deriveBurnConfigPda(configAuthority, tokenMint, configId);
+  const config = await program.account.burnConfig.fetch(burnConfig);
+  const tokenProgram = (await connection.getAccountInfo(tokenMint)).owner;
+  const tokenAccount = getAssociatedTokenAddressSync(tokenMint, wallet.publicKey, false, tokenProgram);
+
   console.log(`\n🔥 Executing Autonomous Burn...`);
   console.log(`   Token: ${tokenMint.toString()}`);
-  console.log(`   Amount: ${burnAmount}`);
-  console.log(`   Profit: ${profitAmount}`);
-  
-  // Derive burn config PDA
-  const [burnConfigPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from('burn_config'), tokenMint.toBuffer()],
-    program.programId
-  );
-  
-  // Get token account
-  const tokenAccount = await getAssociatedTokenAddress(tokenMint, wallet.publicKey);
-  
-  // Execute burn instruction
+  console.log(`   Config: ${burnConfig.toString()}`);
+  console.log(`   Mode: ${JSON.stringify(mode)}`);
+
+  const attestation = await fetchProfitAttestation(burnConfig, config.profitOracle);
+  console.log(`   Profit: ${attestation.profitAmount} (period ${attestation.periodId})`);
+
+  // Unique id for this payment; its receipt makes it single-use
+  const x402Signature = `x402-${crypto.randomUUID()}`;
+  const preInstructions = [attestation.instruction];
+  const paymentAccounts = {
+    paymentReceipt: null,
+    paymentSource: null,
+    paymentMint: null,
+    paymentTreasury: null,
+    paymentTokenProgram: null,
+    creditAccount: null,
+  };
+  if (config.paymentMode.verifiedTransfer) {
+    paymentAccounts.paymentReceipt = derivePaymentReceiptPda(x402Signature);
+    preInstructions.push(await createX402PaymentInstruction(connection, wallet.publicKey, config));
+  } else if (config.paymentMode.programCollected) {
+    const paymentTokenProgram = (await connection.getAccountInfo(config.paymentMint)).owner;
+    paymentAccounts.paymentSource = getAssociatedTokenAddressSync(config.paymentMint, wallet.publicKey, false, paymentTokenProgram);
+    paymentAccounts.paymentMint = config.paymentMint;
+    paymentAccounts.paymentTreasury = config.paymentTreasury;
+    paymentAccounts.paymentTokenProgram = paymentTokenProgram;
+  } else {
+    paymentAccounts.creditAccount = pda([
+      Buffer.from('credit_account'),
+      burnConfig.toBuffer(),
+      wallet.publicKey.toBuffer(),
+    ]);
+  }
+
   const tx = await program.methods
-    .executeAutonomousBurn(
-      new BN(burnAmount),
-      x402Signature,
-      new BN(profitAmount)
-    )
+    .executeAutonomousBurn(mode, x402Signature)
     .accounts({
-      burnConfig: burnConfigPda,
-      tokenMint: tokenMint,
-      tokenAccount: tokenAccount,
-      authority: wallet.publicKey,
-      tokenProgram: TOKEN_PROGRAM_ID,
+      burnConfig,
+      tokenMint,
+      tokenAccount,
+      executor: wallet.publicKey,
+      globalConfig: pda([Buffer.from('global_config')]),
+      burnOperator: config.authority.equals(wallet.publicKey)
+        ? null
+        : pda([Buffer.from('burn_operator'), burnConfig.toBuffer(), wallet.publicKey.toBuffer()]),
+      payer: wallet.publicKey,
+      instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+      ...paymentAccounts,
+      ...(await protocolFeeAccounts(program, config, tokenMint, tokenProgram)),
+      priceFeed: config.priceFeed,
+      pnlLedger: null,
     })
+    .preInstructions(preInstructions)
     .rpc();
-  
+
   console.log(`✅ Burn Transaction: ${tx}`);
-  
+  console.log(`   x402 Payment: ${x402Signature}`);
+
   return tx;
 }
 
@@ -113,47 +206,38 @@ async function executeAutonomousBurn(
 async function runAutonomousAgent() {
   console.log('\n🤖 GigaBrain x402 Autonomous Agent Starting...');
   console.log('━'.repeat(60));
-  
+
   // Setup
   const connection = new Connection(DEVNET_RPC, 'confirmed');
   const wallet = loadWallet();
-  const provider = new AnchorProvider(connection, wallet, {});
-  
-  // Load program (would need actual IDL)
-  // const program = new Program(IDL, PROGRAM_ID, provider);
-  
+  const provider = new AnchorProvider(connection, new Wallet(wallet), {});
+  const idl = JSON.parse(fs.readFileSync(IDL_PATH, 'utf8'));
+  const program = new Program(idl, PROGRAM_ID, provider);
+
+  const tokenMint = new PublicKey(process.env.TOKEN_MINT || '11111111111111111111111111111111'); // Replace with actual
+  const configAuthority = new PublicKey(process.env.CONFIG_AUTHORITY || wallet.publicKey);
+  const configId = Number(process.env.CONFIG_ID || 0);
+  const burnAmount = Number(process.env.BURN_AMOUNT || 1000000); // 1 token (6 decimals)
+
   console.log(`\n📊 Configuration:`);
   console.log(`   Network: Devnet`);
   console.log(`   Wallet: ${wallet.publicKey.toString()}`);
   console.log(`   Program: ${PROGRAM_ID.toString()}`);
-  console.log(`   x402 Fee: $${X402_BURN_FEE} USDC per burn`);
-  
-  // Simulation: Agent detects profit threshold met
-  const simulatedProfit = 1000; // 1000 basis points = 10% profit
-  const tokenMintExample = new PublicKey('11111111111111111111111111111111'); // Replace with actual
-  const burnAmount = 1000000; // 1 token (6 decimals)
-  
-  console.log(`\n🎯 Profit Threshold Met: ${simulatedProfit} basis points`);
-  console.log(`   Autonomous burn triggered!`);
-  
+  console.log(`   Profit Oracle: ${PROFIT_ORACLE_URL}`);
+
   try {
-    // Step 1: Pay for burn service via x402
-    const x402Sig = await createX402Payment(connection, wallet, X402_BURN_FEE);
-    
-    // Step 2: Execute on-chain burn
-    // const burnTx = await executeAutonomousBurn(
-    //   program,
-    //   wallet,
-    //   tokenMintExample,
-    //   burnAmount,
-    //   simulatedProfit,
-    //   x402Sig
-    // );
-    
+    const burnTx = await executeAutonomousBurn(
+      program,
+      wallet,
+      configAuthority,
+      configId,
+      tokenMint,
+      { amount: { amount: new BN(burnAmount) } }
+    );
+
     console.log(`\n✅ Autonomous Burn Complete!`);
-    console.log(`   x402 Payment: ${x402Sig}`);
-    // console.log(`   Burn Transaction: ${burnTx}`);
-    
+    console.log(`   Burn Transaction: ${burnTx}`);
+
   } catch (error) {
     console.error('\n❌ Error:', error.message);
     process.exit(1);
@@ -165,4 +249,4 @@ if (import.meta.url === `file://${process.argv[1]}`) {
   runAutonomousAgent().catch(console.error);
 }
 
-export { createX402Payment, executeAutonomousBurn };
+export { createX402PaymentInstruction, executeAutonomousBurn, fetchProfitAttestation };
@@ -58,9 +58,11 @@ describe('GigaBrain Autonomous Burn Simulation', () => {
   describe('Autonomous Burn Execution', () => {
     it('should derive correct burn config PDA', () => {
       const tokenMint = Keypair.generate().publicKey;
+      const authority = Keypair.generate().publicKey;
+      const configId = Buffer.alloc(8); // u64 little-endian config id 0
       
       const [burnConfigPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('burn_config'), tokenMint.toBuffer()],
+        [Buffer.from('burn_config'), authority.toBuffer(), tokenMint.toBuffer(), configId],
         programId
       );
       